@@ -1,4 +1,7 @@
-use std::sync::{Arc, OnceLock};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex, OnceLock},
+};
 
 use trustfall::{
     provider::{
@@ -9,13 +12,17 @@ use trustfall::{
     FieldValue, Schema,
 };
 
-use super::vertex::Vertex;
+use super::{search::Diagnostic, vertex::Vertex};
 
 static SCHEMA: OnceLock<Schema> = OnceLock::new();
 
 #[non_exhaustive]
 #[derive(Debug)]
-pub struct FileSystemAdapter {}
+pub struct FileSystemAdapter {
+    base_dir: Option<PathBuf>,
+    /// Problems with the queries that have been run, e.g. invalid search patterns
+    diagnostics: Mutex<Vec<Diagnostic>>,
+}
 
 impl Default for FileSystemAdapter {
     fn default() -> Self {
@@ -31,7 +38,23 @@ impl FileSystemAdapter {
     }
 
     pub fn new() -> Self {
-        Self {}
+        Self {
+            base_dir: None,
+            diagnostics: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// New instance that resolves relative paths against the given directory
+    pub fn new_with_base_dir(base_dir: PathBuf) -> Self {
+        Self {
+            base_dir: Some(base_dir),
+            diagnostics: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sets the directory that relative paths are resolved against, instead of the current directory
+    pub fn set_base_dir(&mut self, base_dir: PathBuf) {
+        self.base_dir = Some(base_dir);
     }
 }
 
@@ -45,6 +68,19 @@ impl<'a> trustfall::provider::Adapter<'a> for FileSystemAdapter {
         resolve_info: &ResolveInfo,
     ) -> VertexIterator<'a, Self::Vertex> {
         match edge_name.as_ref() {
+            "Diagnostics" => super::entrypoints::diagnostics(&self.diagnostics, resolve_info),
+            "LanguageSummary" => {
+                let root: &str = parameters
+                    .get("root")
+                    .expect(
+                        "failed to find parameter 'root' when resolving 'LanguageSummary' starting vertices",
+                    )
+                    .as_str()
+                    .expect(
+                        "unexpected null or other incorrect datatype for Trustfall type 'String!'",
+                    );
+                super::entrypoints::language_summary(root, self.base_dir.as_deref(), resolve_info)
+            }
             "Path" => {
                 let path: &str = parameters
                     .get("path")
@@ -55,7 +91,53 @@ impl<'a> trustfall::provider::Adapter<'a> for FileSystemAdapter {
                     .expect(
                         "unexpected null or other incorrect datatype for Trustfall type 'String!'",
                     );
-                super::entrypoints::path(path, resolve_info)
+                super::entrypoints::path(path, self.base_dir.as_deref(), resolve_info)
+            }
+            "Paths" => {
+                let paths: Vec<&str> = parameters
+                    .get("paths")
+                    .expect(
+                        "failed to find parameter 'paths' when resolving 'Paths' starting vertices",
+                    )
+                    .as_vec_with(|value| value.as_str())
+                    .expect(
+                        "unexpected null or other incorrect datatype for Trustfall type '[String!]!'",
+                    );
+                super::entrypoints::paths(paths, self.base_dir.as_deref(), resolve_info)
+            }
+            "Search" => {
+                let root: &str = parameters
+                    .get("root")
+                    .expect(
+                        "failed to find parameter 'root' when resolving 'Search' starting vertices",
+                    )
+                    .as_str()
+                    .expect(
+                        "unexpected null or other incorrect datatype for Trustfall type 'String!'",
+                    );
+                let pattern: &str = parameters
+                    .get("pattern")
+                    .expect(
+                        "failed to find parameter 'pattern' when resolving 'Search' starting vertices",
+                    )
+                    .as_str()
+                    .expect(
+                        "unexpected null or other incorrect datatype for Trustfall type 'String!'",
+                    );
+                let glob: Option<&str> = parameters.get("glob").and_then(|value| value.as_str());
+                let max_results: Option<usize> = parameters
+                    .get("maxResults")
+                    .and_then(|value| value.as_i64())
+                    .map(|value| value.max(0) as usize);
+                super::entrypoints::search(
+                    root,
+                    pattern,
+                    glob,
+                    max_results,
+                    self.base_dir.as_deref(),
+                    &self.diagnostics,
+                    resolve_info,
+                )
             }
             _ => {
                 unreachable!(
@@ -76,6 +158,11 @@ impl<'a> trustfall::provider::Adapter<'a> for FileSystemAdapter {
             return resolve_property_with(contexts, |vertex| vertex.typename().into());
         }
         match type_name.as_ref() {
+            "Diagnostic" => super::properties::resolve_diagnostic_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
             "File" => super::properties::resolve_file_property(
                 contexts,
                 property_name.as_ref(),
@@ -86,6 +173,21 @@ impl<'a> trustfall::provider::Adapter<'a> for FileSystemAdapter {
                 property_name.as_ref(),
                 resolve_info,
             ),
+            "LanguageSummary" => super::properties::resolve_language_summary_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "Match" => super::properties::resolve_match_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "Owner" => super::properties::resolve_owner_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
             "Path" => super::properties::resolve_path_property(
                 contexts,
                 property_name.as_ref(),
@@ -108,12 +210,30 @@ impl<'a> trustfall::provider::Adapter<'a> for FileSystemAdapter {
         resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Self::Vertex>> {
         match type_name.as_ref() {
+            "File" => super::edges::resolve_file_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
             "Folder" => super::edges::resolve_folder_edge(
                 contexts,
                 edge_name.as_ref(),
                 parameters,
                 resolve_info,
             ),
+            "Match" => super::edges::resolve_match_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "Path" => super::edges::resolve_path_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
             _ => {
                 unreachable!(
                     "attempted to resolve edge '{edge_name}' on unexpected type: {type_name}"
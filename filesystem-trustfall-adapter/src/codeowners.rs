@@ -0,0 +1,100 @@
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+/// Locations, relative to a repository root, that are checked for a CODEOWNERS file.
+/// The order matches the precedence GitHub uses when more than one exists.
+const CODEOWNERS_LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+#[derive(Debug, Clone)]
+pub struct Owner {
+    pub handle: String,
+    pub matched_pattern: String,
+}
+
+#[derive(Debug)]
+struct Rule {
+    pattern: String,
+    owners: Vec<String>,
+    matcher: Gitignore,
+}
+
+#[derive(Debug)]
+pub(crate) struct CodeOwners {
+    root: PathBuf,
+    rules: Vec<Rule>,
+}
+
+impl CodeOwners {
+    /// Finds the CODEOWNERS file of the repository the path is in and parses it. Like GitHub,
+    /// only the repository root, the closest directory containing `.git`, is checked.
+    pub(crate) fn discover(path: &Path) -> Option<Self> {
+        let path = path.canonicalize().ok()?;
+        let root = path.ancestors().find(|dir| dir.join(".git").exists())?;
+        let file = CODEOWNERS_LOCATIONS
+            .iter()
+            .map(|location| root.join(location))
+            .find(|file| file.is_file())?;
+        let contents = std::fs::read_to_string(file).ok()?;
+        Some(Self::parse(root.to_path_buf(), &contents))
+    }
+
+    fn parse(root: PathBuf, contents: &str) -> Self {
+        let rules = contents
+            .lines()
+            .filter_map(|line| {
+                let mut parts = strip_comment(line).split_whitespace();
+                let pattern = parts.next()?;
+                let mut builder = GitignoreBuilder::new(&root);
+                builder.add_line(None, pattern).ok()?;
+                Some(Rule {
+                    pattern: pattern.to_string(),
+                    owners: parts.map(|owner| owner.to_string()).collect(),
+                    matcher: builder.build().ok()?,
+                })
+            })
+            .collect();
+        Self { root, rules }
+    }
+
+    /// Owners of the given path. The last matching rule wins, as in `.gitignore` files.
+    pub(crate) fn owners(&self, path: &Path) -> Vec<Owner> {
+        let Ok(path) = path.canonicalize() else {
+            return Vec::new();
+        };
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return Vec::new();
+        };
+        let is_dir = path.is_dir();
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                rule.matcher
+                    .matched_path_or_any_parents(relative, is_dir)
+                    .is_ignore()
+            })
+            .map(|rule| {
+                rule.owners
+                    .iter()
+                    .map(|handle| Owner {
+                        handle: handle.clone(),
+                        matched_pattern: rule.pattern.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '\\' => escaped = !escaped,
+            '#' if !escaped => return &line[..index],
+            _ => escaped = false,
+        }
+    }
+    line
+}
@@ -0,0 +1,52 @@
+use std::{collections::HashSet, fs::Metadata, path::Path};
+
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+
+/// Space allocated on disk, in bytes. This is smaller than the apparent size for sparse files.
+#[cfg(unix)]
+pub(crate) fn allocated_size(metadata: &Metadata) -> u64 {
+    metadata.blocks() * 512
+}
+
+/// Space allocated on disk, in bytes. Block counts aren't available so the apparent size is used.
+#[cfg(not(unix))]
+pub(crate) fn allocated_size(metadata: &Metadata) -> u64 {
+    metadata.len()
+}
+
+/// Identifies a file with more than one hard link so that it is only counted once
+#[cfg(unix)]
+fn hard_link_id(metadata: &Metadata) -> Option<(u64, u64)> {
+    (metadata.nlink() > 1).then(|| (metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn hard_link_id(_metadata: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Total space allocated on disk for the folder and everything below it, like `du`.
+/// Symlinks aren't followed and hard-linked files are only counted once.
+pub(crate) fn disk_usage(root: &Path) -> u64 {
+    let mut seen = HashSet::new();
+    let mut pending = vec![root.to_path_buf()];
+    let mut total = 0;
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = path.symlink_metadata() else {
+            continue;
+        };
+        if let Some(id) = hard_link_id(&metadata) {
+            if !seen.insert(id) {
+                continue;
+            }
+        }
+        total += allocated_size(&metadata);
+        if metadata.is_dir() {
+            if let Ok(entries) = path.read_dir() {
+                pending.extend(entries.flatten().map(|entry| entry.path()));
+            }
+        }
+    }
+    total
+}
@@ -5,6 +5,20 @@ use trustfall::provider::{
 
 use super::vertex::Vertex;
 
+pub(super) fn resolve_file_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "owners" => path::owners(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'File'")
+        }
+    }
+}
+
 pub(super) fn resolve_folder_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
@@ -13,12 +27,41 @@ pub(super) fn resolve_folder_edge<'a, V: AsVertex<Vertex> + 'a>(
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
         "children" => folder::children(contexts, resolve_info),
+        "owners" => path::owners(contexts, resolve_info),
         _ => {
             unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Folder'")
         }
     }
 }
 
+pub(super) fn resolve_match_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "file" => search_match::file(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Match'")
+        }
+    }
+}
+
+pub(super) fn resolve_path_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "owners" => path::owners(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Path'")
+        }
+    }
+}
+
 mod folder {
     use std::path::PathBuf;
 
@@ -42,8 +85,79 @@ mod folder {
                 .unwrap_or_else(|_| panic!("Failed to read dir: {}", folder.to_str().unwrap()));
             let iter = folder_contents
                 .into_iter()
-                .map(|s| Vertex::Path(s.unwrap().path()));
+                .map(|s| Vertex::from_path(s.unwrap().path()));
             Box::new(iter)
         })
     }
 }
+
+mod search_match {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::search::Match;
+
+    use super::super::vertex::{FileNode, Vertex};
+
+    pub(super) fn file<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let search_match: &Match = vertex
+                .as_match()
+                .expect("conversion failed, vertex was not a Match");
+            Box::new(std::iter::once(Vertex::File(FileNode::new(
+                search_match.path.clone(),
+            ))))
+        })
+    }
+}
+
+mod path {
+    use std::{
+        collections::HashMap,
+        path::{Path, PathBuf},
+    };
+
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::codeowners::CodeOwners;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn owners<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        // Siblings share the same CODEOWNERS file, so only look it up once per directory
+        let mut discovered: HashMap<PathBuf, Option<CodeOwners>> = HashMap::new();
+        resolve_neighbors_with(contexts, move |vertex| {
+            let path: &PathBuf = vertex
+                .as_any_path()
+                .expect("conversion failed, vertex was not a Path");
+            let directory = if path.is_dir() {
+                path.clone()
+            } else {
+                path.parent()
+                    .filter(|parent| !parent.as_os_str().is_empty())
+                    .unwrap_or(Path::new("."))
+                    .to_path_buf()
+            };
+            let code_owners = discovered
+                .entry(directory)
+                .or_insert_with_key(|directory| CodeOwners::discover(directory));
+            match code_owners {
+                Some(code_owners) => {
+                    Box::new(code_owners.owners(path).into_iter().map(Vertex::Owner))
+                }
+                None => Box::new(std::iter::empty()),
+            }
+        })
+    }
+}
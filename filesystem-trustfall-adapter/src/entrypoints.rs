@@ -1,14 +1,135 @@
-use std::path::PathBuf;
+use std::{
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
 
 use trustfall::provider::{ResolveInfo, VertexIterator};
 
-use super::vertex::Vertex;
+use super::{
+    search::{self, Diagnostic},
+    stats,
+    vertex::Vertex,
+};
 
-pub(super) fn path<'a>(path: &str, _resolve_info: &ResolveInfo) -> VertexIterator<'a, Vertex> {
-    let path = PathBuf::from(path);
+pub(super) fn path<'a>(
+    path: &str,
+    base_dir: Option<&Path>,
+    _resolve_info: &ResolveInfo,
+) -> VertexIterator<'a, Vertex> {
+    let path = expand_path(path, base_dir);
     if path.exists() {
-        Box::new(std::iter::once(Vertex::Path(path)))
+        Box::new(std::iter::once(Vertex::from_path(path)))
     } else {
         Box::new(std::iter::empty())
     }
 }
+
+pub(super) fn paths<'a>(
+    paths: Vec<&str>,
+    base_dir: Option<&Path>,
+    _resolve_info: &ResolveInfo,
+) -> VertexIterator<'a, Vertex> {
+    let paths: Vec<PathBuf> = paths
+        .into_iter()
+        .map(|path| expand_path(path, base_dir))
+        .filter(|path| path.exists())
+        .collect();
+    Box::new(paths.into_iter().map(Vertex::from_path))
+}
+
+pub(super) fn search<'a>(
+    root: &str,
+    pattern: &str,
+    glob: Option<&str>,
+    max_results: Option<usize>,
+    base_dir: Option<&Path>,
+    diagnostics: &Mutex<Vec<Diagnostic>>,
+    _resolve_info: &ResolveInfo,
+) -> VertexIterator<'a, Vertex> {
+    let root = expand_path(root, base_dir);
+    if !root.exists() {
+        return Box::new(std::iter::empty());
+    }
+    match search::search(&root, pattern, glob, max_results) {
+        Ok(matches) => Box::new(matches.into_iter().map(Vertex::Match)),
+        Err(diagnostic) => {
+            diagnostics.lock().unwrap().push(diagnostic);
+            Box::new(std::iter::empty())
+        }
+    }
+}
+
+pub(super) fn diagnostics<'a>(
+    diagnostics: &Mutex<Vec<Diagnostic>>,
+    _resolve_info: &ResolveInfo,
+) -> VertexIterator<'a, Vertex> {
+    let diagnostics = diagnostics.lock().unwrap().clone();
+    Box::new(diagnostics.into_iter().map(Vertex::Diagnostic))
+}
+
+pub(super) fn language_summary<'a>(
+    root: &str,
+    base_dir: Option<&Path>,
+    _resolve_info: &ResolveInfo,
+) -> VertexIterator<'a, Vertex> {
+    let root = expand_path(root, base_dir);
+    if !root.exists() {
+        return Box::new(std::iter::empty());
+    }
+    let summary = stats::language_summary(&root);
+    Box::new(summary.into_iter().map(Vertex::LanguageSummary))
+}
+
+/// Expands `~`, `$VAR` and `${VAR}` and resolves relative paths against the base directory,
+/// or the current directory if there is none. Variables that aren't set are left as is.
+fn expand_path(path: &str, base_dir: Option<&Path>) -> PathBuf {
+    let path = expand_variables(path);
+    let path = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => match home_dir() {
+            Some(home) => home.join(rest.trim_start_matches(['/', '\\'])),
+            None => PathBuf::from(path),
+        },
+        _ => PathBuf::from(path),
+    };
+    match base_dir {
+        Some(base_dir) if path.is_relative() => base_dir.join(path),
+        _ => path,
+    }
+}
+
+fn expand_variables(path: &str) -> String {
+    let mut expanded = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(index) = rest.find('$') {
+        expanded.push_str(&rest[..index]);
+        rest = &rest[index + 1..];
+        let (name, original, remaining) = match rest.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], &rest[..end + 2], &braced[end + 1..]),
+                None => ("", "", rest),
+            },
+            None => {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                (&rest[..end], &rest[..end], &rest[end..])
+            }
+        };
+        match std::env::var(name) {
+            Ok(value) if !name.is_empty() => expanded.push_str(&value),
+            _ => {
+                expanded.push('$');
+                expanded.push_str(original);
+            }
+        }
+        rest = remaining;
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
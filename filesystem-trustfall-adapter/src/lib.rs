@@ -1,7 +1,11 @@
 mod adapter_impl;
+mod codeowners;
+mod disk_usage;
 mod edges;
 mod entrypoints;
 mod properties;
+mod search;
+mod stats;
 mod vertex;
 
 #[cfg(test)]
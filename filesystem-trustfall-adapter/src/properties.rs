@@ -2,12 +2,103 @@ use sha256::digest;
 
 use trustfall::{
     provider::{
-        resolve_property_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveInfo,
+        field_property, resolve_property_with, AsVertex, ContextIterator, ContextOutcomeIterator,
+        ResolveInfo,
     },
     FieldValue,
 };
 
-use super::vertex::Vertex;
+use super::{
+    disk_usage, stats,
+    vertex::{FileNode, Vertex},
+};
+
+pub(super) fn resolve_diagnostic_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "kind" => resolve_property_with(contexts, field_property!(as_diagnostic, kind)),
+        "message" => resolve_property_with(contexts, field_property!(as_diagnostic, message)),
+        "name" => resolve_property_with(contexts, field_property!(as_diagnostic, name)),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Diagnostic'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_language_summary_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "blankLines" => resolve_property_with(
+            contexts,
+            field_property!(as_language_summary, blank_lines, {
+                (*blank_lines as u64).into()
+            }),
+        ),
+        "codeLines" => resolve_property_with(
+            contexts,
+            field_property!(as_language_summary, code_lines, {
+                (*code_lines as u64).into()
+            }),
+        ),
+        "commentLines" => resolve_property_with(
+            contexts,
+            field_property!(as_language_summary, comment_lines, {
+                (*comment_lines as u64).into()
+            }),
+        ),
+        "files" => resolve_property_with(
+            contexts,
+            field_property!(as_language_summary, files, { (*files as u64).into() }),
+        ),
+        "language" => {
+            resolve_property_with(contexts, field_property!(as_language_summary, language))
+        }
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'LanguageSummary'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_match_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "column" => resolve_property_with(contexts, field_property!(as_match, column)),
+        "line" => resolve_property_with(contexts, field_property!(as_match, line)),
+        "text" => resolve_property_with(contexts, field_property!(as_match, text)),
+        _ => {
+            unreachable!("attempted to read unexpected property '{property_name}' on type 'Match'")
+        }
+    }
+}
+
+pub(super) fn resolve_owner_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "handle" => resolve_property_with(contexts, field_property!(as_owner, handle)),
+        "matchedPattern" => {
+            resolve_property_with(contexts, field_property!(as_owner, matched_pattern))
+        }
+        _ => {
+            unreachable!("attempted to read unexpected property '{property_name}' on type 'Owner'")
+        }
+    }
+}
 
 pub(super) fn resolve_file_property<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
@@ -16,25 +107,57 @@ pub(super) fn resolve_file_property<'a, V: AsVertex<Vertex> + 'a>(
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
         "Hash" => resolve_property_with(contexts, |vertex: &Vertex| match vertex {
-            Vertex::File(path) => {
+            Vertex::File(FileNode { path, .. }) => {
                 let bytes = std::fs::read(path).unwrap();
                 digest(bytes).into()
             }
             _ => unreachable!("Should be Hash"),
         }),
+        "allocatedSize" => resolve_property_with(contexts, |vertex: &Vertex| match vertex {
+            Vertex::File(FileNode { path, .. }) => {
+                // The file may have been removed since it was listed
+                let metadata = path.metadata().ok();
+                metadata.as_ref().map(disk_usage::allocated_size).into()
+            }
+            _ => unreachable!("Should be allocatedSize"),
+        }),
+        "blankLines" => resolve_property_with(contexts, |vertex: &Vertex| match vertex {
+            Vertex::File(file) => (file.source_stats().blanks as u64).into(),
+            _ => unreachable!("Should be blankLines"),
+        }),
+        "codeLines" => resolve_property_with(contexts, |vertex: &Vertex| match vertex {
+            Vertex::File(file) => (file.source_stats().code as u64).into(),
+            _ => unreachable!("Should be codeLines"),
+        }),
+        "commentLines" => resolve_property_with(contexts, |vertex: &Vertex| match vertex {
+            Vertex::File(file) => (file.source_stats().comments as u64).into(),
+            _ => unreachable!("Should be commentLines"),
+        }),
         "extension" => resolve_property_with(contexts, |vertex: &Vertex| match vertex {
-            Vertex::File(path) => path.extension().unwrap().to_str().unwrap().into(),
+            Vertex::File(FileNode { path, .. }) => path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .into(),
             _ => unreachable!("Should be extension"),
         }),
-        "path" => resolve_property_with(contexts, |vertex: &Vertex| match vertex {
-            Vertex::File(path) => path.to_str().unwrap().into(),
-            Vertex::Folder(path) => path.to_str().unwrap().into(),
-            Vertex::Path(path) => path.to_str().unwrap().into(),
+        "language" => resolve_property_with(contexts, |vertex: &Vertex| match vertex {
+            Vertex::File(FileNode { path, .. }) => {
+                stats::language(path).map(|language| language.name()).into()
+            }
+            _ => unreachable!("Should be language"),
+        }),
+        "path" => resolve_property_with(contexts, |vertex: &Vertex| match vertex.as_any_path() {
+            Some(path) => path.to_str().unwrap().into(),
+            None => unreachable!("Should be path"),
+        }),
+        "shebang" => resolve_property_with(contexts, |vertex: &Vertex| match vertex {
+            Vertex::File(FileNode { path, .. }) => stats::shebang(path).into(),
+            _ => unreachable!("Should be shebang"),
         }),
         "size" => resolve_property_with(contexts, |vertex: &Vertex| match vertex {
-            Vertex::File(path) => {
+            Vertex::File(FileNode { path, .. }) => {
                 // if path.is_file() {
-                path.metadata().unwrap().len().into()
+                path.metadata().ok().map(|metadata| metadata.len()).into()
                 // if cfg!(target_os = "windows") {
                 // } else {
                 //     path.metadata().unwrap().size()
@@ -55,9 +178,13 @@ pub(super) fn resolve_folder_property<'a, V: AsVertex<Vertex> + 'a>(
     _resolve_info: &ResolveInfo,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
-        "path" => resolve_property_with(contexts, |vertex: &Vertex| match vertex {
-            Vertex::Folder(path) => path.to_str().unwrap().into(),
-            _ => unreachable!("Should be path"),
+        "diskUsage" => resolve_property_with(contexts, |vertex: &Vertex| match vertex {
+            Vertex::Folder(path) => disk_usage::disk_usage(path).into(),
+            _ => unreachable!("Should be diskUsage"),
+        }),
+        "path" => resolve_property_with(contexts, |vertex: &Vertex| match vertex.as_any_path() {
+            Some(path) => path.to_str().unwrap().into(),
+            None => unreachable!("Should be path"),
         }),
         _ => {
             unreachable!("attempted to read unexpected property '{property_name}' on type 'Folder'")
@@ -71,9 +198,9 @@ pub(super) fn resolve_path_property<'a, V: AsVertex<Vertex> + 'a>(
     _resolve_info: &ResolveInfo,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
-        "path" => resolve_property_with(contexts, |vertex: &Vertex| match vertex {
-            Vertex::Path(path) => path.to_str().unwrap().into(),
-            _ => unreachable!("Should be path"),
+        "path" => resolve_property_with(contexts, |vertex: &Vertex| match vertex.as_any_path() {
+            Some(path) => path.to_str().unwrap().into(),
+            None => unreachable!("Should be path"),
         }),
         _ => {
             unreachable!("attempted to read unexpected property '{property_name}' on type 'Path'")
@@ -0,0 +1,120 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use grep_matcher::Matcher;
+use grep_regex::RegexMatcher;
+use grep_searcher::{sinks::Lossy, BinaryDetection, SearcherBuilder};
+use ignore::{overrides::OverrideBuilder, WalkBuilder, WalkState};
+
+#[derive(Debug, Clone)]
+pub struct Match {
+    pub path: PathBuf,
+    pub line: u64,
+    pub column: u64,
+    pub text: String,
+}
+
+/// A problem with a query's input, such as a search pattern that isn't a valid regex
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// What kind of problem it is, e.g. `invalid-search-pattern`
+    pub kind: String,
+    /// What the problem is about, e.g. the pattern
+    pub name: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn new(kind: &str, name: &str, message: String) -> Self {
+        Self {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            message,
+        }
+    }
+}
+
+/// Searches every file below `root` for `pattern` in parallel, the same way ripgrep does.
+/// Hidden, ignored and binary files are skipped. If `glob` is given only matching files are searched.
+/// A pattern or glob that can't be parsed gives a diagnostic rather than matches.
+pub(crate) fn search(
+    root: &Path,
+    pattern: &str,
+    glob: Option<&str>,
+    max_results: Option<usize>,
+) -> Result<Vec<Match>, Diagnostic> {
+    let matcher = RegexMatcher::new_line_matcher(pattern).map_err(|e| {
+        let message = format!("Invalid search pattern '{pattern}': {e}");
+        Diagnostic::new("invalid-search-pattern", pattern, message)
+    })?;
+    let mut walker = WalkBuilder::new(root);
+    if let Some(glob) = glob {
+        let mut overrides = OverrideBuilder::new(root);
+        let overrides = overrides
+            .add(glob)
+            .and_then(|overrides| overrides.build())
+            .map_err(|e| {
+                let message = format!("Invalid search glob '{glob}': {e}");
+                Diagnostic::new("invalid-search-glob", glob, message)
+            })?;
+        walker.overrides(overrides);
+    }
+    let max_results = max_results.unwrap_or(usize::MAX);
+    let found = AtomicUsize::new(0);
+    let matches = Mutex::new(Vec::new());
+
+    walker.build_parallel().run(|| {
+        let matcher = matcher.clone();
+        let mut searcher = SearcherBuilder::new()
+            .binary_detection(BinaryDetection::quit(b'\x00'))
+            .line_number(true)
+            .build();
+        let found = &found;
+        let matches = &matches;
+        Box::new(move |entry| {
+            if found.load(Ordering::Relaxed) >= max_results {
+                return WalkState::Quit;
+            }
+            let Ok(entry) = entry else {
+                return WalkState::Continue;
+            };
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                return WalkState::Continue;
+            }
+            let mut file_matches = Vec::new();
+            let result = searcher.search_path(
+                &matcher,
+                entry.path(),
+                Lossy(|line_number, line| {
+                    let text = line.trim_end_matches(['\r', '\n']);
+                    let before = file_matches.len();
+                    let _ = matcher.find_iter(text.as_bytes(), |m| {
+                        file_matches.push(Match {
+                            path: entry.path().to_path_buf(),
+                            line: line_number,
+                            column: m.start() as u64 + 1,
+                            text: text.to_string(),
+                        });
+                        true
+                    });
+                    let added = file_matches.len() - before;
+                    Ok(found.fetch_add(added, Ordering::Relaxed) + added < max_results)
+                }),
+            );
+            if result.is_ok() && !file_matches.is_empty() {
+                matches.lock().unwrap().append(&mut file_matches);
+            }
+            WalkState::Continue
+        })
+    });
+
+    let mut matches = matches.into_inner().unwrap();
+    matches.sort_by(|a, b| (&a.path, a.line, a.column).cmp(&(&b.path, b.line, b.column)));
+    matches.truncate(max_results);
+    Ok(matches)
+}
@@ -0,0 +1,78 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use tokei::{Config, LanguageType, Languages};
+
+/// Line counts of a single source file
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SourceStats {
+    pub code: usize,
+    pub comments: usize,
+    pub blanks: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct LanguageSummary {
+    pub language: String,
+    pub files: usize,
+    pub code_lines: usize,
+    pub comment_lines: usize,
+    pub blank_lines: usize,
+}
+
+/// Detects the language of a file from its name, extension or shebang
+pub(crate) fn language(path: &Path) -> Option<LanguageType> {
+    LanguageType::from_path(path, &Config::default())
+}
+
+/// Counts the code, comment and blank lines of a file. Files in an unknown language count as empty.
+pub(crate) fn source_stats(path: &Path) -> SourceStats {
+    let config = Config::default();
+    let Some(language) = LanguageType::from_path(path, &config) else {
+        return SourceStats::default();
+    };
+    match language.parse(path.to_path_buf(), &config) {
+        Ok(report) => {
+            // Fold embedded languages (e.g. code blocks in Markdown) into the file's totals
+            let stats = report.stats.summarise();
+            SourceStats {
+                code: stats.code,
+                comments: stats.comments,
+                blanks: stats.blanks,
+            }
+        }
+        Err(_) => SourceStats::default(),
+    }
+}
+
+/// The interpreter line of a script, e.g. `#!/usr/bin/env python3`
+pub(crate) fn shebang(path: &Path) -> Option<String> {
+    let mut line = String::new();
+    BufReader::new(File::open(path).ok()?)
+        .read_line(&mut line)
+        .ok()?;
+    line.starts_with("#!").then(|| line.trim_end().to_string())
+}
+
+/// Aggregates the line counts of every non-ignored file below `root` per language
+pub(crate) fn language_summary(root: &Path) -> Vec<LanguageSummary> {
+    let mut languages = Languages::new();
+    languages.get_statistics(&[root], &[], &Config::default());
+    languages
+        .iter()
+        .filter(|(_, language)| !language.is_empty())
+        .map(|(language_type, language)| {
+            let language = language.summarise();
+            LanguageSummary {
+                language: language_type.name().to_string(),
+                files: language.reports.len(),
+                code_lines: language.code,
+                comment_lines: language.comments,
+                blank_lines: language.blanks,
+            }
+        })
+        .collect()
+}
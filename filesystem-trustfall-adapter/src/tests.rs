@@ -1,4 +1,6 @@
-use trustfall::provider::check_adapter_invariants;
+use std::{collections::BTreeMap, sync::Arc};
+
+use trustfall::{execute_query, provider::check_adapter_invariants, FieldValue};
 
 use super::FileSystemAdapter;
 
@@ -8,3 +10,419 @@ fn adapter_satisfies_trustfall_invariants() {
     let schema = FileSystemAdapter::schema();
     check_adapter_invariants(schema, adapter);
 }
+
+#[test]
+fn owners_use_last_matching_codeowners_rule() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+    std::fs::create_dir_all(dir.path().join(".github")).unwrap();
+    std::fs::create_dir_all(dir.path().join("infra")).unwrap();
+    std::fs::write(
+        dir.path().join(".github/CODEOWNERS"),
+        "* @org/default\n/infra/ @org/platform @alice # platform team\n*.md @org/docs\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("infra/main.tf"), "").unwrap();
+    std::fs::write(dir.path().join("infra/README.md"), "").unwrap();
+    // Only the repository root is checked for a CODEOWNERS file
+    std::fs::write(dir.path().join("infra/CODEOWNERS"), "* @org/infra\n").unwrap();
+    // A nested repository isn't covered by the CODEOWNERS file of the one around it
+    std::fs::create_dir_all(dir.path().join("vendor/lib/.git")).unwrap();
+    std::fs::write(dir.path().join("vendor/lib/main.rs"), "").unwrap();
+
+    let owners = |file: &str| {
+        let query = format!(
+            r#"
+            query {{
+                Path(path: {:?}) {{
+                    owners {{
+                        handle @output
+                        matchedPattern @output
+                    }}
+                }}
+            }}"#,
+            dir.path().join(file).to_str().unwrap()
+        );
+        execute_query(
+            FileSystemAdapter::schema(),
+            Arc::new(FileSystemAdapter::new()),
+            &query,
+            BTreeMap::<Arc<str>, FieldValue>::new(),
+        )
+        .unwrap()
+        .map(|row| {
+            (
+                row["handle"].as_str().unwrap().to_string(),
+                row["matchedPattern"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        owners("infra/main.tf"),
+        vec![
+            ("@org/platform".to_string(), "/infra/".to_string()),
+            ("@alice".to_string(), "/infra/".to_string()),
+        ]
+    );
+    assert_eq!(
+        owners("infra/README.md"),
+        vec![("@org/docs".to_string(), "*.md".to_string())]
+    );
+    assert_eq!(owners("vendor/lib/main.rs"), vec![]);
+}
+
+#[test]
+fn search_skips_ignored_and_binary_files() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    std::fs::create_dir_all(dir.path().join("target")).unwrap();
+    std::fs::write(dir.path().join(".ignore"), "target/\n").unwrap();
+    std::fs::write(
+        dir.path().join("src/lib.rs"),
+        "fn main() {}\n// TODO: a TODO\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("src/notes.md"), "TODO: docs\n").unwrap();
+    std::fs::write(dir.path().join("target/out.rs"), "// TODO\n").unwrap();
+    std::fs::write(dir.path().join("src/blob.rs"), b"TODO\x00\x01").unwrap();
+
+    let search = |arguments: &str| {
+        let query = format!(
+            r#"
+            query {{
+                Search(root: {:?}, pattern: "TODO"{arguments}) {{
+                    line @output
+                    column @output
+                    text @output
+                    file {{
+                        path @output
+                    }}
+                }}
+            }}"#,
+            dir.path().to_str().unwrap()
+        );
+        execute_query(
+            FileSystemAdapter::schema(),
+            Arc::new(FileSystemAdapter::new()),
+            &query,
+            BTreeMap::<Arc<str>, FieldValue>::new(),
+        )
+        .unwrap()
+        .map(|row| {
+            let path = std::path::PathBuf::from(row["path"].as_str().unwrap());
+            (
+                path.file_name().unwrap().to_str().unwrap().to_string(),
+                row["line"].as_u64().unwrap(),
+                row["column"].as_u64().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        search(r#", glob: "*.rs""#),
+        vec![("lib.rs".to_string(), 2, 4), ("lib.rs".to_string(), 2, 12)]
+    );
+    assert_eq!(
+        search(""),
+        vec![
+            ("lib.rs".to_string(), 2, 4),
+            ("lib.rs".to_string(), 2, 12),
+            ("notes.md".to_string(), 1, 1),
+        ]
+    );
+    assert_eq!(search(", maxResults: 1").len(), 1);
+}
+
+#[test]
+fn invalid_search_input_is_reported_instead_of_searched() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("notes.md"), "TODO: docs\n").unwrap();
+    let adapter = Arc::new(FileSystemAdapter::new());
+    let run = |query: &str| {
+        execute_query(
+            FileSystemAdapter::schema(),
+            adapter.clone(),
+            query,
+            BTreeMap::<Arc<str>, FieldValue>::new(),
+        )
+        .unwrap()
+        .collect::<Vec<_>>()
+    };
+    let search = |arguments: &str| {
+        run(&format!(
+            r#"
+            query {{
+                Search(root: {:?}, {arguments}) {{
+                    line @output
+                }}
+            }}"#,
+            dir.path().to_str().unwrap()
+        ))
+    };
+
+    assert!(search(r#"pattern: "TODO(""#).is_empty());
+    assert!(search(r#"pattern: "TODO", glob: "[""#).is_empty());
+    let diagnostics: Vec<_> = run(r#"
+        query {
+            Diagnostics {
+                kind @output
+                name @output
+                message @output
+            }
+        }"#)
+    .into_iter()
+    .map(|row| {
+        (
+            row["kind"].as_str().unwrap().to_string(),
+            row["name"].as_str().unwrap().to_string(),
+            row["message"].as_str().unwrap().to_string(),
+        )
+    })
+    .collect();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].0, "invalid-search-pattern");
+    assert_eq!(diagnostics[0].1, "TODO(");
+    assert!(diagnostics[0]
+        .2
+        .starts_with("Invalid search pattern 'TODO(': "));
+    assert_eq!(diagnostics[1].0, "invalid-search-glob");
+    assert_eq!(diagnostics[1].1, "[");
+}
+
+#[test]
+fn source_stats_are_counted_per_file_and_language() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("main.rs"),
+        "// entry point\nfn main() {\n\n    println!(\"hi\");\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("run"),
+        "#!/usr/bin/env python3\n# comment\nprint('hi')\n",
+    )
+    .unwrap();
+
+    let run = |query: String| {
+        execute_query(
+            FileSystemAdapter::schema(),
+            Arc::new(FileSystemAdapter::new()),
+            &query,
+            BTreeMap::<Arc<str>, FieldValue>::new(),
+        )
+        .unwrap()
+        .collect::<Vec<_>>()
+    };
+
+    let rows = run(format!(
+        r#"
+        query {{
+            Path(path: {:?}) {{
+                ... on File {{
+                    extension @output
+                    size @output
+                    language @output
+                    shebang @output
+                    codeLines @output
+                    commentLines @output
+                    blankLines @output
+                }}
+            }}
+        }}"#,
+        dir.path().join("run").to_str().unwrap()
+    ));
+    assert_eq!(rows[0]["extension"], FieldValue::Null);
+    assert_eq!(rows[0]["size"], 45u64.into());
+    assert_eq!(rows[0]["language"], "Python".into());
+    assert_eq!(rows[0]["shebang"], "#!/usr/bin/env python3".into());
+    assert_eq!(rows[0]["codeLines"], 1u64.into());
+    assert_eq!(rows[0]["commentLines"], 2u64.into());
+    assert_eq!(rows[0]["blankLines"], 0u64.into());
+
+    let rows = run(format!(
+        r#"
+        query {{
+            LanguageSummary(root: {:?}) {{
+                language @output
+                files @output
+                codeLines @output
+                commentLines @output
+                blankLines @output
+            }}
+        }}"#,
+        dir.path().to_str().unwrap()
+    ));
+    let rust = rows
+        .iter()
+        .find(|row| row["language"] == "Rust".into())
+        .unwrap();
+    assert_eq!(rust["files"], 1u64.into());
+    assert_eq!(rust["codeLines"], 3u64.into());
+    assert_eq!(rust["commentLines"], 1u64.into());
+    assert_eq!(rust["blankLines"], 1u64.into());
+}
+
+#[test]
+fn paths_are_expanded_against_the_base_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("services/api")).unwrap();
+    std::fs::write(dir.path().join("services/api/main.rs"), "").unwrap();
+    std::env::set_var("FS_ADAPTER_TEST_SERVICE", "api");
+
+    let query = r#"
+    query {
+        Paths(paths: ["services/$FS_ADAPTER_TEST_SERVICE", "services/${FS_ADAPTER_TEST_SERVICE}/main.rs", "missing"]) {
+            path @output
+        }
+    }"#;
+    let paths: Vec<_> = execute_query(
+        FileSystemAdapter::schema(),
+        Arc::new(FileSystemAdapter::new_with_base_dir(
+            dir.path().to_path_buf(),
+        )),
+        query,
+        BTreeMap::<Arc<str>, FieldValue>::new(),
+    )
+    .unwrap()
+    .map(|row| std::path::PathBuf::from(row["path"].as_str().unwrap()))
+    .collect();
+
+    assert_eq!(
+        paths,
+        vec![
+            dir.path().join("services/api"),
+            dir.path().join("services/api/main.rs"),
+        ]
+    );
+}
+
+#[test]
+fn files_without_an_extension_can_be_listed() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("Makefile"), "all:\n").unwrap();
+    std::fs::write(dir.path().join("main.rs"), "").unwrap();
+
+    let query = format!(
+        r#"
+        query {{
+            Path(path: {:?}) {{
+                ... on Folder {{
+                    children {{
+                        ... on File {{
+                            path @output
+                            extension @output
+                        }}
+                    }}
+                }}
+            }}
+        }}"#,
+        dir.path().to_str().unwrap()
+    );
+    let mut extensions: Vec<_> = execute_query(
+        FileSystemAdapter::schema(),
+        Arc::new(FileSystemAdapter::new()),
+        &query,
+        BTreeMap::<Arc<str>, FieldValue>::new(),
+    )
+    .unwrap()
+    .map(|row| (row["path"].clone(), row["extension"].clone()))
+    .collect();
+    extensions.sort_by_key(|(path, _)| path.as_str().unwrap().to_string());
+
+    assert_eq!(
+        extensions,
+        vec![
+            (
+                dir.path().join("Makefile").to_str().unwrap().into(),
+                FieldValue::Null
+            ),
+            (
+                dir.path().join("main.rs").to_str().unwrap().into(),
+                "rs".into()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn sizes_are_null_for_files_removed_after_being_listed() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("scratch.txt");
+    std::fs::write(&file, "temporary").unwrap();
+
+    let query = format!(
+        r#"
+        query {{
+            Path(path: {:?}) {{
+                ... on File {{
+                    size @output
+                    allocatedSize @output
+                }}
+            }}
+        }}"#,
+        file.to_str().unwrap()
+    );
+    let rows = execute_query(
+        FileSystemAdapter::schema(),
+        Arc::new(FileSystemAdapter::new()),
+        &query,
+        BTreeMap::<Arc<str>, FieldValue>::new(),
+    )
+    .unwrap();
+    std::fs::remove_file(&file).unwrap();
+    let rows: Vec<_> = rows.collect();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["size"], FieldValue::Null);
+    assert_eq!(rows[0]["allocatedSize"], FieldValue::Null);
+}
+
+#[cfg(unix)]
+#[test]
+fn disk_usage_counts_hard_links_once() {
+    use std::os::unix::fs::MetadataExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let folder = dir.path().join("layer");
+    std::fs::create_dir_all(&folder).unwrap();
+    std::fs::write(folder.join("data.bin"), vec![1u8; 64 * 1024]).unwrap();
+    std::fs::hard_link(folder.join("data.bin"), folder.join("link.bin")).unwrap();
+
+    let query = format!(
+        r#"
+        query {{
+            Path(path: {:?}) {{
+                ... on Folder {{
+                    diskUsage @output
+                    children {{
+                        ... on File {{
+                            size @output
+                            allocatedSize @output
+                        }}
+                    }}
+                }}
+            }}
+        }}"#,
+        folder.to_str().unwrap()
+    );
+    let rows: Vec<_> = execute_query(
+        FileSystemAdapter::schema(),
+        Arc::new(FileSystemAdapter::new()),
+        &query,
+        BTreeMap::<Arc<str>, FieldValue>::new(),
+    )
+    .unwrap()
+    .collect();
+
+    let file_blocks = folder.join("data.bin").metadata().unwrap().blocks() * 512;
+    let folder_blocks = folder.metadata().unwrap().blocks() * 512;
+    assert_eq!(rows.len(), 2);
+    for row in &rows {
+        assert_eq!(row["size"], (64u64 * 1024).into());
+        assert_eq!(row["allocatedSize"], file_blocks.into());
+        assert_eq!(row["diskUsage"], (folder_blocks + file_blocks).into());
+    }
+}
@@ -1,9 +1,66 @@
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    sync::{Arc, OnceLock},
+};
+
+use super::{
+    codeowners::Owner,
+    search::{Diagnostic, Match},
+    stats::{self, LanguageSummary, SourceStats},
+};
 
 #[non_exhaustive]
 #[derive(Debug, Clone, trustfall::provider::TrustfallEnumVertex)]
 pub enum Vertex {
-    File(PathBuf),
+    Diagnostic(Diagnostic),
+    File(FileNode),
     Folder(PathBuf),
+    LanguageSummary(LanguageSummary),
+    Match(Match),
+    Owner(Owner),
     Path(PathBuf),
 }
+
+impl Vertex {
+    /// Creates the most specific vertex for the path
+    pub(crate) fn from_path(path: PathBuf) -> Self {
+        if path.is_file() {
+            Vertex::File(FileNode::new(path))
+        } else if path.is_dir() {
+            Vertex::Folder(path)
+        } else {
+            Vertex::Path(path)
+        }
+    }
+
+    /// The underlying path of any `Path` vertex
+    pub(crate) fn as_any_path(&self) -> Option<&PathBuf> {
+        match self {
+            Vertex::File(file) => Some(&file.path),
+            Vertex::Folder(path) | Vertex::Path(path) => Some(path),
+            _ => None,
+        }
+    }
+}
+
+/// A file along with its line counts, which are counted the first time one is asked for so that
+/// reading several of them only parses the file once
+#[derive(Debug, Clone)]
+pub struct FileNode {
+    pub path: PathBuf,
+    source_stats: Arc<OnceLock<SourceStats>>,
+}
+
+impl FileNode {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            source_stats: Arc::default(),
+        }
+    }
+
+    pub(crate) fn source_stats(&self) -> &SourceStats {
+        self.source_stats
+            .get_or_init(|| stats::source_stats(&self.path))
+    }
+}
@@ -1,8 +1,12 @@
 use std::{
-    path::PathBuf,
+    collections::HashMap,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
     sync::{Arc, OnceLock},
 };
 
+use indexmap::IndexMap;
 use trustfall::{
     provider::{
         resolve_coercion_using_schema, resolve_property_with, AsVertex, ContextIterator,
@@ -12,19 +16,34 @@ use trustfall::{
     FieldValue, Schema,
 };
 
-use crate::{errors::OpenAPIAdapterErrors, utils::find_files};
+use crate::{
+    errors::OpenAPIAdapterErrors,
+    lint::{self, LintRule, LintViolation},
+    refs::file_key,
+    template::Template,
+    utils::{find_files, is_json_document, Diagnostic},
+};
 
 use super::{
-    utils::{merge, Route},
+    document::Document,
+    spans::{file_positions, positions},
+    utils::{merge, split},
     vertex::Vertex,
 };
 
 static SCHEMA: OnceLock<Schema> = OnceLock::new();
 
 #[non_exhaustive]
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct OpenApiAdapter {
-    openapi: openapiv3::OpenAPI,
+    pub(crate) document: Arc<Document>,
+    /// Every document, when specs are loaded as separate documents
+    documents: Vec<Arc<Document>>,
+    diagnostics: Vec<Diagnostic>,
+    template_variables: Option<HashMap<String, String>>,
+    separate_documents: bool,
+    /// Whether JSON files that don't declare a version are merged when a directory is loaded
+    json_fragments: bool,
 }
 
 impl OpenApiAdapter {
@@ -53,12 +72,64 @@ impl OpenApiAdapter {
         Ok(adapter)
     }
 
+    /// New instance from a YAML or JSON document that is read from the reader
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self, OpenAPIAdapterErrors> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+        contents.parse()
+    }
+
+    /// New instance from an already parsed OpenAPI document
+    pub fn from_openapi(openapi: openapiv3::OpenAPI) -> Self {
+        Self {
+            document: Arc::new(Document::from_openapi(openapi)),
+            ..Default::default()
+        }
+    }
+
+    /// Values for the `${name}` placeholders of specs that are templates, e.g. Terraform
+    /// `templatefile` inputs. `$${` is an escape for a literal `${`.
+    ///
+    /// The placeholders are replaced when files are loaded, so this has to be set before
+    /// [`files`](Self::files) or [`set_path`](Self::set_path). Placeholders without a value are
+    /// left as they are and reported by the `Diagnostics` entrypoint.
+    pub fn with_template_variables(mut self, variables: HashMap<String, String>) -> Self {
+        self.template_variables = Some(variables);
+        self
+    }
+
+    /// Loads each file that declares an OpenAPI or Swagger version as a document of its own,
+    /// rather than merging the files into one, e.g. for a directory with a spec per service.
+    ///
+    /// The documents are listed by the `Documents` entrypoint, in order of their source file.
+    /// The other entrypoints query the first of them. Like
+    /// [`with_template_variables`](Self::with_template_variables), this has to be set before
+    /// files are loaded.
+    pub fn with_separate_documents(mut self) -> Self {
+        self.separate_documents = true;
+        self
+    }
+
+    /// Merges every `.json` file that [`set_path`](Self::set_path) finds, rather than only the
+    /// ones that declare an OpenAPI or Swagger version, for a spec split into JSON fragments.
+    ///
+    /// This is off by default, since a directory often holds JSON that isn't part of the spec,
+    /// e.g. `package.json`. Fragments that are referenced with a `$ref` are loaded either way.
+    pub fn with_json_fragments(mut self) -> Self {
+        self.json_fragments = true;
+        self
+    }
+
     /// Set the files that are to be used
+    ///
+    /// Relative `$ref`s are resolved against the file they're in
     pub fn files(&mut self, files: Vec<PathBuf>) -> Result<(), OpenAPIAdapterErrors> {
-        let merged_content = merge(files)?;
-        self.openapi = serde_yaml::from_str(&merged_content)
-            .map_err(OpenAPIAdapterErrors::FailedToSerializeToOpenAPI)?;
-        Ok(())
+        let root = files
+            .first()
+            .and_then(|file| file.parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        self.load(files, &root)
     }
 
     /// Sets the directory that files are to be found
@@ -68,45 +139,212 @@ impl OpenApiAdapter {
         }
         let mut files = find_files(&path, "yaml".as_ref());
         files.extend(find_files(&path, "yml".as_ref()));
+        let (json, skipped): (Vec<_>, Vec<_>) = find_files(&path, "json".as_ref())
+            .into_iter()
+            .partition(|file| self.json_fragments || is_json_document(file));
+        files.extend(json);
         if files.is_empty() {
             return Err(OpenAPIAdapterErrors::FilesNotFound(path));
         }
-        let merged_content = merge(files)?;
-        self.openapi = serde_yaml::from_str(&merged_content)
-            .map_err(OpenAPIAdapterErrors::FailedToSerializeToOpenAPI)?;
+        self.load(files, &path)?;
+        // Skipped JSON is reported, unless a `$ref` loaded it anyway
+        for file in skipped {
+            let key = file_key(&file, &path)?;
+            if !self.document.external.contains_key(&key) {
+                self.diagnostics.push(Diagnostic {
+                    kind: "skipped-json-file".to_string(),
+                    message: format!(
+                        "Skipped {key}, which doesn't declare an openapi or swagger version. \
+                        Use with_json_fragments to merge it"
+                    ),
+                    name: key,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn load(&mut self, files: Vec<PathBuf>, root: &Path) -> Result<(), OpenAPIAdapterErrors> {
+        if self.separate_documents {
+            return self.load_separately(files, root);
+        }
+        let (merged_content, external, provenance) = merge(files.clone(), root)?;
+        let positions = file_positions(&files, root, &external);
+        let mut template = Template::new(self.template_variables.as_ref());
+        let mut document = Document::from_str(&template.substitute(merged_content))?;
+        document.external = template.substitute_external(external);
+        document.provenance = provenance;
+        document.positions = positions;
+        self.document = Arc::new(document);
+        self.documents = Vec::new();
+        self.diagnostics = template.diagnostics();
         Ok(())
     }
 
+    fn load_separately(
+        &mut self,
+        files: Vec<PathBuf>,
+        root: &Path,
+    ) -> Result<(), OpenAPIAdapterErrors> {
+        let (mut contents, external) = split(files.clone(), root)?;
+        let positions = file_positions(&files, root, &external);
+        if contents.is_empty() {
+            return Err(OpenAPIAdapterErrors::FilesNotFound(root.to_path_buf()));
+        }
+        contents.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut template = Template::new(self.template_variables.as_ref());
+        let external = template.substitute_external(external);
+        let mut documents = Vec::new();
+        for (key, content) in contents {
+            let mut document = Document::from_str(&template.substitute(content))?;
+            document.external = external.clone();
+            document.source_file = Some(key);
+            document.positions = positions.clone();
+            documents.push(Arc::new(document));
+        }
+        self.document = documents[0].clone();
+        self.documents = documents;
+        self.diagnostics = template.diagnostics();
+        Ok(())
+    }
+
+    /// Checks the spec against the bundled lint rules, giving the violations of each rule in
+    /// turn. See [`LintRule::bundled`] for the rules
+    pub fn lint(&self) -> Result<Vec<LintViolation>, OpenAPIAdapterErrors> {
+        self.lint_with(&LintRule::bundled())
+    }
+
+    /// Checks the spec against the lint rules, e.g. the bundled rules along with ones read by
+    /// [`LintRule::from_path`]. A rule whose query isn't valid gives an error
+    pub fn lint_with(
+        &self,
+        rules: &[LintRule],
+    ) -> Result<Vec<LintViolation>, OpenAPIAdapterErrors> {
+        lint::lint(Arc::new(self.clone()), rules)
+    }
+
+    fn components<'a>(&self) -> VertexIterator<'a, Vertex> {
+        let iter = self
+            .document
+            .components()
+            .into_iter()
+            .map(Vertex::Component);
+        Box::new(iter)
+    }
+
+    fn diagnostics<'a>(&self) -> VertexIterator<'a, Vertex> {
+        let mut diagnostics = self.diagnostics.clone();
+        // Separate documents share the files they reference, so their problems can repeat
+        let documents = std::iter::once(&self.document).chain(&self.documents);
+        for diagnostic in documents.flat_map(|document| document.diagnostics()) {
+            if !diagnostics
+                .iter()
+                .any(|found| found.name == diagnostic.name)
+            {
+                diagnostics.push(diagnostic);
+            }
+        }
+        Box::new(diagnostics.into_iter().map(Vertex::Diagnostic))
+    }
+
+    fn document(&self) -> Vertex {
+        Vertex::Document(self.document.clone())
+    }
+
+    fn documents<'a>(&self) -> VertexIterator<'a, Vertex> {
+        if self.documents.is_empty() {
+            return Box::new(std::iter::once(self.document()));
+        }
+        let iter = self.documents.clone().into_iter().map(Vertex::Document);
+        Box::new(iter)
+    }
+
     fn info(&self) -> Vertex {
-        Vertex::Info(self.openapi.info.clone())
+        Vertex::Info(self.document.openapi.info.clone())
+    }
+
+    fn merge_conflicts<'a>(&self) -> VertexIterator<'a, Vertex> {
+        let iter = self
+            .document
+            .provenance
+            .conflicts
+            .clone()
+            .into_iter()
+            .map(Vertex::MergeConflict);
+        Box::new(iter)
     }
 
     fn path(&self, path: &str) -> Vertex {
-        let mut route: Route = self
+        let item = self
+            .document
             .openapi
             .paths
             .paths
             .get(path)
-            .expect("path not found")
-            .clone()
-            .into();
-        route.path = path.to_string();
-        Vertex::Path(route)
+            .expect("path not found");
+        Vertex::Path(Box::new(self.document.route(path, item)))
     }
 
     fn paths<'a>(&self) -> VertexIterator<'a, Vertex> {
-        let iter = self.openapi.paths.clone().into_iter().map(|x| {
-            let mut route: Route = x.1.into();
-            route.path = x.0.clone().to_string();
-            Vertex::Path(route)
-        });
+        let iter = self
+            .document
+            .routes()
+            .into_iter()
+            .map(|route| Vertex::Path(Box::new(route)));
+        Box::new(iter)
+    }
+
+    fn schemas<'a>(&self) -> VertexIterator<'a, Vertex> {
+        let iter = self.document.schemas().into_iter().map(Vertex::Schema);
+        Box::new(iter)
+    }
+
+    fn security_schemes<'a>(&self) -> VertexIterator<'a, Vertex> {
+        let iter = self
+            .document
+            .security_schemes()
+            .into_iter()
+            .map(Vertex::security_scheme);
+        Box::new(iter)
+    }
+
+    fn servers<'a>(&self) -> VertexIterator<'a, Vertex> {
+        let iter = self
+            .document
+            .openapi
+            .servers
+            .clone()
+            .into_iter()
+            .map(Vertex::Server);
         Box::new(iter)
     }
 
     fn tags<'a>(&self) -> VertexIterator<'a, Vertex> {
-        let iter = self.openapi.tags.clone().into_iter().map(Vertex::Tag);
+        let iter = self.document.tags().into_iter().map(Vertex::Tag);
+        Box::new(iter)
+    }
+
+    fn webhooks<'a>(&self) -> VertexIterator<'a, Vertex> {
+        let iter = self
+            .document
+            .webhook_routes()
+            .into_iter()
+            .map(|route| Vertex::Path(Box::new(route)));
         Box::new(iter)
-        // Vertex::Tags(self.openapi.tags.clone())
+    }
+}
+
+impl FromStr for OpenApiAdapter {
+    type Err = OpenAPIAdapterErrors;
+
+    /// New instance from a YAML or JSON document
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        let mut document = Document::from_str(contents)?;
+        document.positions = IndexMap::from([(String::new(), positions(contents))]);
+        Ok(Self {
+            document: Arc::new(document),
+            ..Default::default()
+        })
     }
 }
 
@@ -120,7 +358,12 @@ impl<'a> trustfall::provider::Adapter<'a> for OpenApiAdapter {
         _resolve_info: &ResolveInfo,
     ) -> VertexIterator<'a, Self::Vertex> {
         match edge_name.as_ref() {
+            "Components" => self.components(),
+            "Diagnostics" => self.diagnostics(),
+            "Document" => Box::new(std::iter::once(self.document())),
+            "Documents" => self.documents(),
             "Info" => Box::new(std::iter::once(self.info())),
+            "MergeConflicts" => self.merge_conflicts(),
             "Path" => {
                 let path: &str = parameters
                     .get("path")
@@ -136,9 +379,13 @@ impl<'a> trustfall::provider::Adapter<'a> for OpenApiAdapter {
             }
             // "Paths" => super::entrypoints::paths(resolve_info, &self.openapi),
             "Paths" => self.paths(),
+            "Schemas" => self.schemas(),
+            "SecuritySchemes" => self.security_schemes(),
+            "Servers" => self.servers(),
             // "Tags" => super::entrypoints::tags(resolve_info, &self.openapi),
             // "Tags" => Box::new(std::iter::once(self.tags())),
             "Tags" => self.tags(),
+            "Webhooks" => self.webhooks(),
             _ => {
                 unreachable!(
                     "attempted to resolve starting vertices for unexpected edge name: {edge_name}"
@@ -158,6 +405,25 @@ impl<'a> trustfall::provider::Adapter<'a> for OpenApiAdapter {
             return resolve_property_with(contexts, |vertex| vertex.typename().into());
         }
         match type_name.as_ref() {
+            "AmazonApigatewayAuthorizer" => {
+                super::properties::resolve_amazon_apigateway_authorizer_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                )
+            }
+            "AmazonApigatewayCors" => super::properties::resolve_amazon_apigateway_cors_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "AmazonApigatewayGatewayResponse" => {
+                super::properties::resolve_amazon_apigateway_gateway_response_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                )
+            }
             "AmazonApigatewayIntegration" => {
                 super::properties::resolve_amazon_apigateway_integration_property(
                     contexts,
@@ -165,26 +431,191 @@ impl<'a> trustfall::provider::Adapter<'a> for OpenApiAdapter {
                     resolve_info,
                 )
             }
+            "AmazonApigatewayIntegrationArn" => {
+                super::properties::resolve_amazon_apigateway_integration_arn_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                )
+            }
+            "AmazonApigatewayIntegrationResponse" => {
+                super::properties::resolve_amazon_apigateway_integration_response_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                )
+            }
+            "AmazonApigatewayMapping" => {
+                super::properties::resolve_amazon_apigateway_mapping_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                )
+            }
+            "AmazonApigatewayRequestValidator" => {
+                super::properties::resolve_amazon_apigateway_request_validator_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                )
+            }
+            "Callback" => super::properties::resolve_callback_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "Change" => super::properties::resolve_change_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "Component" => super::properties::resolve_component_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "Diagnostic" => super::properties::resolve_diagnostic_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "Document" => super::properties::resolve_document_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "Example" => super::properties::resolve_example_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "Extension" => super::properties::resolve_extension_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "ExternalDocs" => super::properties::resolve_external_docs_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "Header" => super::properties::resolve_header_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
             "Info" => super::properties::resolve_info_property(
                 contexts,
                 property_name.as_ref(),
                 resolve_info,
             ),
+            "JsonValue" => super::properties::resolve_json_value_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "Link" => super::properties::resolve_link_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "Located" => super::properties::resolve_located_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "MediaType" => super::properties::resolve_media_type_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "MergeConflict" => super::properties::resolve_merge_conflict_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "OAuthFlow" => super::properties::resolve_oauth_flow_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "OAuthScope" => super::properties::resolve_oauth_scope_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
             "Operation" => super::properties::resolve_operation_property(
                 contexts,
                 property_name.as_ref(),
                 resolve_info,
             ),
+            "Parameter" => super::properties::resolve_parameter_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
             "Path" => super::properties::resolve_path_property(
                 contexts,
                 property_name.as_ref(),
                 resolve_info,
             ),
+            "RequestBody" => super::properties::resolve_request_body_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "Response" => super::properties::resolve_response_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "Schema" => super::properties::resolve_schema_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "SchemaProperty" => super::properties::resolve_schema_property_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "ApiKeySecurityScheme"
+            | "HttpSecurityScheme"
+            | "OAuth2SecurityScheme"
+            | "OpenIdConnectSecurityScheme"
+            | "SecurityScheme" => super::properties::resolve_security_scheme_property(
+                contexts,
+                type_name.as_ref(),
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "SecuritySchemeRequirement" => {
+                super::properties::resolve_security_scheme_requirement_property(
+                    contexts,
+                    property_name.as_ref(),
+                    resolve_info,
+                )
+            }
+            "Server" => super::properties::resolve_server_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
+            "ServerVariable" => super::properties::resolve_server_variable_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
             "Tag" => super::properties::resolve_tag_property(
                 contexts,
                 property_name.as_ref(),
                 resolve_info,
             ),
+            "ValidationError" => super::properties::resolve_validation_error_property(
+                contexts,
+                property_name.as_ref(),
+                resolve_info,
+            ),
             _ => {
                 unreachable!(
                     "attempted to read property '{property_name}' on unexpected type: {type_name}"
@@ -202,18 +633,175 @@ impl<'a> trustfall::provider::Adapter<'a> for OpenApiAdapter {
         resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Self::Vertex>> {
         match type_name.as_ref() {
+            "AmazonApigatewayAuthorizer" => {
+                super::edges::resolve_amazon_apigateway_authorizer_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                )
+            }
+            "AmazonApigatewayGatewayResponse" => {
+                super::edges::resolve_amazon_apigateway_gateway_response_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                )
+            }
+            "AmazonApigatewayIntegration" => {
+                super::edges::resolve_amazon_apigateway_integration_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                )
+            }
+            "AmazonApigatewayIntegrationResponse" => {
+                super::edges::resolve_amazon_apigateway_integration_response_edge(
+                    contexts,
+                    edge_name.as_ref(),
+                    parameters,
+                    resolve_info,
+                )
+            }
+            "Callback" => super::edges::resolve_callback_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "Change" => super::edges::resolve_change_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "Document" => super::edges::resolve_document_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "Example" => super::edges::resolve_example_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "Extension" => super::edges::resolve_extension_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "Header" => super::edges::resolve_header_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "Info" => super::edges::resolve_info_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "JsonValue" => super::edges::resolve_json_value_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "Link" => super::edges::resolve_link_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "MediaType" => super::edges::resolve_media_type_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "OAuthFlow" => super::edges::resolve_oauth_flow_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
             "Operation" => super::edges::resolve_operation_edge(
                 contexts,
                 edge_name.as_ref(),
                 parameters,
                 resolve_info,
             ),
+            "Parameter" => super::edges::resolve_parameter_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
             "Path" => super::edges::resolve_path_edge(
                 contexts,
                 edge_name.as_ref(),
                 parameters,
                 resolve_info,
             ),
+            "RequestBody" => super::edges::resolve_request_body_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "Response" => super::edges::resolve_response_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "Schema" => super::edges::resolve_schema_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "SchemaProperty" => super::edges::resolve_schema_property_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "ApiKeySecurityScheme"
+            | "HttpSecurityScheme"
+            | "OAuth2SecurityScheme"
+            | "OpenIdConnectSecurityScheme"
+            | "SecurityScheme" => super::edges::resolve_security_scheme_edge(
+                contexts,
+                type_name.as_ref(),
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "SecurityRequirement" => super::edges::resolve_security_requirement_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "SecuritySchemeRequirement" => super::edges::resolve_security_scheme_requirement_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
+            "Server" => super::edges::resolve_server_edge(
+                contexts,
+                edge_name.as_ref(),
+                parameters,
+                resolve_info,
+            ),
             _ => {
                 unreachable!(
                     "attempted to resolve edge '{edge_name}' on unexpected type: {type_name}"
@@ -0,0 +1,349 @@
+use indexmap::IndexMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    refs::escape,
+    utils::{Diagnostic, Named},
+};
+
+/// The `x-amazon-apigateway-integration` extension of an operation
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmazonApigatewayIntegration {
+    #[serde(rename = "type")]
+    pub r_type: String,
+    pub http_method: Option<String>,
+    pub uri: Option<String>,
+    #[serde(rename = "passthroughBehavior")]
+    pub pass_through_behavior: Option<String>,
+    pub timeout_in_millis: Option<usize>,
+    pub credentials: Option<String>,
+    pub connection_type: Option<String>,
+    pub connection_id: Option<String>,
+    pub content_handling: Option<String>,
+    #[serde(default)]
+    pub cache_key_parameters: Vec<String>,
+    pub cache_namespace: Option<String>,
+    pub integration_subtype: Option<String>,
+    #[serde(default)]
+    pub request_parameters: IndexMap<String, String>,
+    #[serde(default)]
+    pub request_templates: IndexMap<String, String>,
+    /// Integration responses keyed by their selection pattern
+    #[serde(default)]
+    pub responses: IndexMap<String, IntegrationResponse>,
+    #[serde(skip)]
+    pub trigger: String,
+    #[serde(skip)]
+    pub arn: Option<String>,
+    #[serde(skip)]
+    pub uri_arn: Option<IntegrationArn>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrationResponse {
+    pub status_code: String,
+    #[serde(default)]
+    pub response_parameters: IndexMap<String, String>,
+    #[serde(default)]
+    pub response_templates: IndexMap<String, String>,
+    pub content_handling: Option<String>,
+}
+
+/// The `x-amazon-apigateway-authorizer` extension of a security scheme
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmazonApigatewayAuthorizer {
+    /// token, request, cognito_user_pools or jwt
+    #[serde(rename = "type")]
+    pub r_type: String,
+    pub authorizer_uri: Option<String>,
+    pub authorizer_credentials: Option<String>,
+    pub identity_source: Option<String>,
+    pub identity_validation_expression: Option<String>,
+    #[serde(rename = "authorizerResultTtlInSeconds")]
+    pub result_ttl_in_seconds: Option<u64>,
+    #[serde(rename = "providerARNs", default)]
+    pub provider_arns: Vec<String>,
+    #[serde(rename = "authorizerPayloadFormatVersion")]
+    pub payload_format_version: Option<String>,
+    pub enable_simple_responses: Option<bool>,
+    #[serde(skip)]
+    pub uri_arn: Option<IntegrationArn>,
+}
+
+/// An entry of the `x-amazon-apigateway-request-validators` extension
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestValidator {
+    #[serde(default)]
+    pub validate_request_body: bool,
+    #[serde(default)]
+    pub validate_request_parameters: bool,
+}
+
+/// The `x-amazon-apigateway-cors` extension
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmazonApigatewayCors {
+    #[serde(default)]
+    pub allow_origins: Vec<String>,
+    #[serde(default)]
+    pub allow_methods: Vec<String>,
+    #[serde(default)]
+    pub allow_headers: Vec<String>,
+    #[serde(default)]
+    pub expose_headers: Vec<String>,
+    #[serde(default)]
+    pub allow_credentials: bool,
+    pub max_age: Option<i64>,
+}
+
+/// An entry of the `x-amazon-apigateway-gateway-responses` extension
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayResponse {
+    pub status_code: Option<String>,
+    #[serde(default)]
+    pub response_parameters: IndexMap<String, String>,
+    #[serde(default)]
+    pub response_templates: IndexMap<String, String>,
+}
+
+/// The API's request validators, from the document's `x-amazon-apigateway-request-validators`
+pub fn request_validators(extensions: &IndexMap<String, Value>) -> Vec<Named<RequestValidator>> {
+    extension::<IndexMap<String, RequestValidator>>(
+        extensions,
+        "x-amazon-apigateway-request-validators",
+    )
+    .unwrap_or_default()
+    .into_iter()
+    .map(|(name, item)| Named { name, item })
+    .collect()
+}
+
+/// The request validator named by an `x-amazon-apigateway-request-validator` extension, looked
+/// up in the document's validators
+pub fn request_validator(
+    extensions: &IndexMap<String, Value>,
+    document_extensions: &IndexMap<String, Value>,
+) -> Option<Named<RequestValidator>> {
+    let name: String = extension(extensions, "x-amazon-apigateway-request-validator")?;
+    request_validators(document_extensions)
+        .into_iter()
+        .find(|validator| validator.name == name)
+}
+
+/// Deserializes an extension, or `None` when it's missing or malformed. Malformed extensions
+/// are reported by [`extension_diagnostics`]
+pub fn extension<T: DeserializeOwned>(
+    extensions: &IndexMap<String, Value>,
+    name: &str,
+) -> Option<T> {
+    let value = extensions.get(name)?;
+    serde_json::from_value(value.clone()).ok()
+}
+
+/// Adds the `x-amazon-apigateway-*` extensions within a value that can't be read to the
+/// diagnostics. The value is at the location, e.g. `#` for a whole document
+pub(crate) fn extension_diagnostics(
+    value: &Value,
+    location: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                let location = format!("{location}/{}", escape(key));
+                if !key.starts_with("x-amazon-apigateway-") {
+                    extension_diagnostics(value, &location, diagnostics);
+                } else if let Some(e) = extension_error(key, value) {
+                    diagnostics.push(Diagnostic {
+                        kind: "invalid-extension".to_string(),
+                        name: location,
+                        message: format!("Failed to deserialize {key}: {e}"),
+                    });
+                }
+            }
+        }
+        Value::Array(values) => {
+            for (i, value) in values.iter().enumerate() {
+                extension_diagnostics(value, &format!("{location}/{i}"), diagnostics);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Why an extension can't be read as what it's read as, if it can't. Extensions that aren't
+/// read are never wrong
+fn extension_error(name: &str, value: &Value) -> Option<serde_json::Error> {
+    fn error<T: DeserializeOwned>(value: &Value) -> Option<serde_json::Error> {
+        serde_json::from_value::<T>(value.clone()).err()
+    }
+    match name {
+        "x-amazon-apigateway-api-key-source" => error::<String>(value),
+        "x-amazon-apigateway-authorizer" => error::<AmazonApigatewayAuthorizer>(value),
+        "x-amazon-apigateway-binary-media-types" => error::<Vec<String>>(value),
+        "x-amazon-apigateway-cors" => error::<AmazonApigatewayCors>(value),
+        "x-amazon-apigateway-gateway-responses" => {
+            error::<IndexMap<String, GatewayResponse>>(value)
+        }
+        "x-amazon-apigateway-integration" => error::<AmazonApigatewayIntegration>(value),
+        "x-amazon-apigateway-request-validator" => error::<String>(value),
+        "x-amazon-apigateway-request-validators" => {
+            error::<IndexMap<String, RequestValidator>>(value)
+        }
+        _ => None,
+    }
+}
+
+impl AmazonApigatewayAuthorizer {
+    /// The authorizer of a security scheme's extensions
+    pub fn from_extensions(extensions: &IndexMap<String, Value>) -> Option<Self> {
+        let mut authorizer: Self = extension(extensions, "x-amazon-apigateway-authorizer")?;
+        authorizer.uri_arn = authorizer
+            .authorizer_uri
+            .as_deref()
+            .and_then(|uri| IntegrationArn::parse(uri, &IndexMap::new()));
+        Some(authorizer)
+    }
+
+    /// The authorizer's result TTL, which API Gateway defaults to 300 seconds for Lambda
+    /// authorizers
+    pub fn effective_result_ttl(&self) -> Option<u64> {
+        match self.r_type.to_lowercase().as_str() {
+            "token" | "request" => Some(self.result_ttl_in_seconds.unwrap_or(300)),
+            _ => self.result_ttl_in_seconds,
+        }
+    }
+}
+
+/// An integration URI of the form `arn:aws:apigateway:{region}:{service}:{action type}/{action}`
+#[derive(Debug, Clone)]
+pub struct IntegrationArn {
+    pub region: String,
+    pub service: String,
+    /// `path` or `action`
+    pub action_type: String,
+    pub action: String,
+    /// The Lambda function's ARN, for Lambda integrations
+    pub function_arn: Option<String>,
+    pub function_name: Option<String>,
+    /// The state machine's ARN from the request templates, for Step Functions integrations
+    pub state_machine_arn: Option<String>,
+}
+
+impl AmazonApigatewayIntegration {
+    /// Works out what the integration triggers from its type and URI
+    pub fn extract_supplementary_data(&mut self) {
+        self.uri_arn = self
+            .uri
+            .as_deref()
+            .and_then(|uri| IntegrationArn::parse(uri, &self.request_templates));
+        self.arn = self
+            .uri_arn
+            .as_ref()
+            .and_then(|arn| arn.function_arn.as_deref())
+            .map(|function_arn| {
+                placeholder(function_arn)
+                    .unwrap_or(function_arn)
+                    .to_string()
+            });
+        self.trigger = match (self.r_type.to_lowercase().as_str(), &self.uri_arn) {
+            ("mock", _) => "Mock".to_owned(),
+            ("http" | "http_proxy", _) => "HTTP".to_owned(),
+            (_, Some(arn)) => match arn.service.as_str() {
+                "lambda" => "Lambda".to_owned(),
+                "states" => "Step Function".to_owned(),
+                service => service.to_owned(),
+            },
+            (r_type, None) => r_type.to_owned(),
+        };
+    }
+}
+
+impl IntegrationArn {
+    /// Parses an API Gateway integration ARN, or `None` when the URI isn't one, e.g. for HTTP
+    /// integrations
+    pub fn parse(uri: &str, request_templates: &IndexMap<String, String>) -> Option<Self> {
+        let segments = split_arn(uri, 6);
+        let [arn, _partition, apigateway, region, service, rest] = segments.as_slice() else {
+            return None;
+        };
+        if *arn != "arn" || *apigateway != "apigateway" {
+            return None;
+        }
+        let (action_type, action) = rest.split_once('/').unwrap_or(("", rest));
+        let function_arn = action.split_once("/functions/").map(|(_, function)| {
+            function
+                .strip_suffix("/invocations")
+                .unwrap_or(function)
+                .to_string()
+        });
+        let function_name = function_arn.as_deref().map(|function_arn| {
+            match placeholder(function_arn) {
+                Some(name) => name,
+                // arn:aws:lambda:{region}:{account}:function:{name}[:{alias}]
+                None => split_arn(function_arn, 8)
+                    .get(6)
+                    .copied()
+                    .unwrap_or(function_arn),
+            }
+            .to_string()
+        });
+        let state_machine_arn = match *service {
+            "states" => request_templates
+                .values()
+                .find_map(|template| json_string_field(template, "stateMachineArn")),
+            _ => None,
+        };
+        Some(Self {
+            region: region.to_string(),
+            service: service.to_string(),
+            action_type: action_type.to_string(),
+            action: action.to_string(),
+            function_arn,
+            function_name,
+            state_machine_arn,
+        })
+    }
+}
+
+/// Splits an ARN on `:` into at most `limit` segments, ignoring colons within `${...}`
+/// placeholders such as `${AWS::Region}`
+fn split_arn(arn: &str, limit: usize) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let bytes = arn.as_bytes();
+    for (i, byte) in bytes.iter().enumerate() {
+        match byte {
+            b'{' if i > 0 && bytes[i - 1] == b'$' => depth += 1,
+            b'}' if depth > 0 => depth -= 1,
+            b':' if depth == 0 && segments.len() + 1 < limit => {
+                segments.push(&arn[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push(&arn[start..]);
+    segments
+}
+
+/// The name inside a value that is entirely a `${...}` placeholder
+fn placeholder(value: &str) -> Option<&str> {
+    value.strip_prefix("${")?.strip_suffix('}')
+}
+
+/// The string value of a field in a JSON-like template, which may not be valid JSON because of
+/// template expressions elsewhere in it
+fn json_string_field(template: &str, field: &str) -> Option<String> {
+    let (_, rest) = template.split_once(&format!("\"{field}\""))?;
+    let (_, rest) = rest.trim_start().strip_prefix(':')?.split_once('"')?;
+    let (value, _) = rest.split_once('"')?;
+    Some(value.to_string())
+}
@@ -0,0 +1,816 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, OnceLock},
+};
+
+use openapiv3::{MediaType, Parameter};
+use serde_json::{Map, Value};
+use trustfall::{
+    provider::{
+        Adapter, AsVertex, ContextIterator, ContextOutcomeIterator, EdgeParameters,
+        ResolveEdgeInfo, ResolveInfo, VertexIterator,
+    },
+    FieldValue, Schema,
+};
+
+use crate::{
+    adapter_impl::OpenApiAdapter,
+    document::Document,
+    refs::Resolved,
+    utils::{
+        media_type_schema, media_types, parameter_location, parameter_schema, Named, Operator,
+        Route, SchemaNode,
+    },
+    vertex::Vertex,
+};
+
+/// Keywords that bound a value from above, so lowering or adding one narrows what's valid
+const UPPER_BOUNDS: [&str; 4] = ["maximum", "maxLength", "maxItems", "maxProperties"];
+
+/// Keywords that bound a value from below, so raising or adding one narrows what's valid
+const LOWER_BOUNDS: [&str; 4] = ["minimum", "minLength", "minItems", "minProperties"];
+
+static SCHEMA: OnceLock<Schema> = OnceLock::new();
+
+/// Compares two versions of a spec. The `Changes` entrypoint lists what changed from the old
+/// spec to the new one, and the other entrypoints query the new spec.
+///
+/// Both specs can be loaded in any of the ways an [`OpenApiAdapter`] can.
+#[derive(Debug)]
+pub struct OpenApiDiffAdapter {
+    adapter: OpenApiAdapter,
+    changes: Vec<Change>,
+}
+
+impl OpenApiDiffAdapter {
+    /// What the adapter adds to [`OpenApiAdapter`]'s schema: the `Changes` entrypoint and the
+    /// types it leads to
+    pub const SCHEMA_EXTENSION_TEXT: &'static str = include_str!("./diff_schema.graphql");
+
+    /// Get the defined schema for the adapter, which is [`OpenApiAdapter`]'s along with
+    /// [`SCHEMA_EXTENSION_TEXT`](Self::SCHEMA_EXTENSION_TEXT)
+    pub fn schema() -> &'static Schema {
+        SCHEMA.get_or_init(|| Schema::parse(schema_text()).expect("not a valid schema"))
+    }
+
+    pub fn new(old: OpenApiAdapter, new: OpenApiAdapter) -> Self {
+        let mut differ = Differ::default();
+        differ.documents(&old.document, &new.document);
+        Self {
+            adapter: new,
+            changes: differ.changes,
+        }
+    }
+}
+
+/// [`OpenApiAdapter`]'s schema with the extension's fields added to its root query type, since
+/// Trustfall doesn't read `extend type`
+fn schema_text() -> String {
+    let base = OpenApiAdapter::SCHEMA_TEXT;
+    let (fields, types) = OpenApiDiffAdapter::SCHEMA_EXTENSION_TEXT
+        .strip_prefix("extend type RootSchemaQuery {")
+        .and_then(|extension| extension.split_once("\n}"))
+        .expect("the schema extension extends RootSchemaQuery");
+    let root = base
+        .find("type RootSchemaQuery {")
+        .expect("the schema has a root query type");
+    let end = root
+        + base[root..]
+            .find("\n}")
+            .expect("the root query type is closed");
+    format!("{}{fields}{}{types}", &base[..end], &base[end..])
+}
+
+/// Whether a change can break the API's existing clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Breaking,
+    NonBreaking,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Breaking => "breaking",
+            Severity::NonBreaking => "non-breaking",
+        }
+    }
+}
+
+/// A difference between the old and new versions of a spec
+#[derive(Debug, Clone)]
+pub struct Change {
+    /// What changed, e.g. `pathRemoved` or `enumValueAdded`
+    pub kind: &'static str,
+    pub severity: Severity,
+    /// What the change is in, e.g. `GET /users/{id} response 200 application/json`, followed
+    /// by the property for a change within a schema
+    pub location: String,
+    pub message: String,
+    /// The changed item in the old spec, unless it was added
+    pub old: Option<Box<Vertex>>,
+    /// The changed item in the new spec, unless it was removed
+    pub new: Option<Box<Vertex>>,
+}
+
+/// Whether a schema describes what clients send or what they receive, which decides whether
+/// narrowing it breaks them
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    Request,
+    Response,
+}
+
+impl Direction {
+    /// Severity of the schema accepting fewer values than it did
+    fn narrowed(self) -> Severity {
+        match self {
+            Direction::Request => Severity::Breaking,
+            Direction::Response => Severity::NonBreaking,
+        }
+    }
+
+    /// Severity of the schema accepting more values than it did
+    fn widened(self) -> Severity {
+        match self {
+            Direction::Request => Severity::NonBreaking,
+            Direction::Response => Severity::Breaking,
+        }
+    }
+
+    fn narrowed_kind(self) -> &'static str {
+        match self {
+            Direction::Request => "requestSchemaNarrowed",
+            Direction::Response => "responseSchemaNarrowed",
+        }
+    }
+
+    fn widened_kind(self) -> &'static str {
+        match self {
+            Direction::Request => "requestSchemaWidened",
+            Direction::Response => "responseSchemaWidened",
+        }
+    }
+}
+
+/// Collects the changes between two documents, walking them path by path
+#[derive(Default)]
+struct Differ {
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn push(
+        &mut self,
+        kind: &'static str,
+        severity: Severity,
+        location: &str,
+        message: String,
+        old: Option<Vertex>,
+        new: Option<Vertex>,
+    ) {
+        self.changes.push(Change {
+            kind,
+            severity,
+            location: location.to_string(),
+            message,
+            old: old.map(Box::new),
+            new: new.map(Box::new),
+        });
+    }
+
+    fn documents(&mut self, old: &Arc<Document>, new: &Arc<Document>) {
+        let old_routes = old.routes();
+        let new_routes = new.routes();
+        for old_route in &old_routes {
+            match new_routes.iter().find(|route| route.path == old_route.path) {
+                Some(new_route) => self.routes(old_route, new_route),
+                None => self.push(
+                    "pathRemoved",
+                    Severity::Breaking,
+                    &old_route.path,
+                    "path was removed".to_string(),
+                    Some(Vertex::Path(Box::new(old_route.clone()))),
+                    None,
+                ),
+            }
+        }
+        for new_route in &new_routes {
+            if !old_routes.iter().any(|route| route.path == new_route.path) {
+                self.push(
+                    "pathAdded",
+                    Severity::NonBreaking,
+                    &new_route.path,
+                    "path was added".to_string(),
+                    None,
+                    Some(Vertex::Path(Box::new(new_route.clone()))),
+                );
+            }
+        }
+    }
+
+    fn routes(&mut self, old: &Route, new: &Route) {
+        let old_operations = old.operations();
+        let new_operations = new.operations();
+        for old_operation in &old_operations {
+            let location = format!("{} {}", old_operation.method, old.path);
+            let new_operation = new_operations
+                .iter()
+                .find(|operation| operation.method == old_operation.method);
+            match new_operation {
+                Some(new_operation) => self.operations(&location, old_operation, new_operation),
+                None => self.push(
+                    "operationRemoved",
+                    Severity::Breaking,
+                    &location,
+                    "operation was removed".to_string(),
+                    Some(Vertex::Operation((*old_operation).clone())),
+                    None,
+                ),
+            }
+        }
+        for new_operation in &new_operations {
+            if !old_operations
+                .iter()
+                .any(|operation| operation.method == new_operation.method)
+            {
+                self.push(
+                    "operationAdded",
+                    Severity::NonBreaking,
+                    &format!("{} {}", new_operation.method, new.path),
+                    "operation was added".to_string(),
+                    None,
+                    Some(Vertex::Operation((*new_operation).clone())),
+                );
+            }
+        }
+    }
+
+    fn operations(&mut self, location: &str, old: &Operator, new: &Operator) {
+        self.parameters(location, old, new);
+        self.request_bodies(location, old, new);
+        self.responses(location, old, new);
+    }
+
+    fn parameters(&mut self, location: &str, old: &Operator, new: &Operator) {
+        let old_parameters = old.effective_parameters();
+        let new_parameters = new.effective_parameters();
+        let location_of = |parameter: &Resolved<Parameter>| {
+            let data = parameter.item.parameter_data_ref();
+            let kind = parameter_location(&parameter.item);
+            format!("{location} {kind} parameter {}", data.name)
+        };
+        for old_parameter in &old_parameters {
+            let location = location_of(old_parameter);
+            let new_parameter = new_parameters
+                .iter()
+                .find(|parameter| location_of(parameter) == location);
+            let Some(new_parameter) = new_parameter else {
+                self.push(
+                    "parameterRemoved",
+                    Severity::Breaking,
+                    &location,
+                    "parameter was removed".to_string(),
+                    Some(Vertex::Parameter(old_parameter.clone())),
+                    None,
+                );
+                continue;
+            };
+            let vertices = || {
+                (
+                    Some(Vertex::Parameter(old_parameter.clone())),
+                    Some(Vertex::Parameter(new_parameter.clone())),
+                )
+            };
+            let was_required = old_parameter.item.parameter_data_ref().required;
+            let is_required = new_parameter.item.parameter_data_ref().required;
+            if is_required != was_required {
+                let (kind, severity, message) = if is_required {
+                    ("parameterBecameRequired", Severity::Breaking, "required")
+                } else {
+                    ("parameterBecameOptional", Severity::NonBreaking, "optional")
+                };
+                let (old, new) = vertices();
+                let message = format!("parameter became {message}");
+                self.push(kind, severity, &location, message, old, new);
+            }
+            if let (Some(old), Some(new)) = (
+                parameter_schema(old_parameter),
+                parameter_schema(new_parameter),
+            ) {
+                self.root_schemas(&location, &old, &new, Direction::Request);
+            }
+        }
+        for new_parameter in &new_parameters {
+            let location = location_of(new_parameter);
+            if old_parameters
+                .iter()
+                .any(|parameter| location_of(parameter) == location)
+            {
+                continue;
+            }
+            let (kind, severity, message) = if new_parameter.item.parameter_data_ref().required {
+                (
+                    "requiredParameterAdded",
+                    Severity::Breaking,
+                    "required parameter was added",
+                )
+            } else {
+                (
+                    "parameterAdded",
+                    Severity::NonBreaking,
+                    "optional parameter was added",
+                )
+            };
+            let new = Some(Vertex::Parameter(new_parameter.clone()));
+            self.push(kind, severity, &location, message.to_string(), None, new);
+        }
+    }
+
+    fn request_bodies(&mut self, location: &str, old: &Operator, new: &Operator) {
+        let location = format!("{location} request body");
+        // Request bodies aren't located, so changes to them lead to their operations
+        let old_vertex = Vertex::Operation(old.clone());
+        let new_vertex = Vertex::Operation(new.clone());
+        match (old.request_body(), new.request_body()) {
+            (None, None) => {}
+            (None, Some(new)) => {
+                let (kind, severity, message) = if new.item.required {
+                    (
+                        "requiredRequestBodyAdded",
+                        Severity::Breaking,
+                        "required request body was added",
+                    )
+                } else {
+                    (
+                        "requestBodyAdded",
+                        Severity::NonBreaking,
+                        "optional request body was added",
+                    )
+                };
+                let message = message.to_string();
+                self.push(kind, severity, &location, message, None, Some(new_vertex));
+            }
+            (Some(_), None) => self.push(
+                "requestBodyRemoved",
+                Severity::Breaking,
+                &location,
+                "request body was removed".to_string(),
+                Some(old_vertex),
+                None,
+            ),
+            (Some(old), Some(new)) => {
+                if new.item.required != old.item.required {
+                    let (kind, severity, message) = if new.item.required {
+                        ("requestBodyBecameRequired", Severity::Breaking, "required")
+                    } else {
+                        (
+                            "requestBodyBecameOptional",
+                            Severity::NonBreaking,
+                            "optional",
+                        )
+                    };
+                    self.push(
+                        kind,
+                        severity,
+                        &location,
+                        format!("request body became {message}"),
+                        Some(old_vertex.clone()),
+                        Some(new_vertex.clone()),
+                    );
+                }
+                let old_content =
+                    media_types(&old.item.content, old.location.as_deref(), &old.document);
+                let new_content =
+                    media_types(&new.item.content, new.location.as_deref(), &new.document);
+                let vertices = (&old_vertex, &new_vertex);
+                self.content(
+                    &location,
+                    &old_content,
+                    &new_content,
+                    vertices,
+                    Direction::Request,
+                );
+            }
+        }
+    }
+
+    fn responses(&mut self, location: &str, old: &Operator, new: &Operator) {
+        let old_responses = old.responses();
+        let new_responses = new.responses();
+        for old_response in &old_responses {
+            let location = format!("{location} response {}", old_response.name);
+            let old_vertex = Vertex::Response(old_response.clone());
+            let new_response = new_responses
+                .iter()
+                .find(|response| response.name == old_response.name);
+            let Some(new_response) = new_response else {
+                self.push(
+                    "responseRemoved",
+                    Severity::Breaking,
+                    &location,
+                    "response was removed".to_string(),
+                    Some(old_vertex),
+                    None,
+                );
+                continue;
+            };
+            let new_vertex = Vertex::Response(new_response.clone());
+            let content = |response: &Named<Resolved<openapiv3::Response>>| {
+                let response = &response.item;
+                media_types(
+                    &response.item.content,
+                    response.location.as_deref(),
+                    &response.document,
+                )
+            };
+            self.content(
+                &location,
+                &content(old_response),
+                &content(new_response),
+                (&old_vertex, &new_vertex),
+                Direction::Response,
+            );
+        }
+        for new_response in &new_responses {
+            if !old_responses
+                .iter()
+                .any(|response| response.name == new_response.name)
+            {
+                self.push(
+                    "responseAdded",
+                    Severity::NonBreaking,
+                    &format!("{location} response {}", new_response.name),
+                    "response was added".to_string(),
+                    None,
+                    Some(Vertex::Response(new_response.clone())),
+                );
+            }
+        }
+    }
+
+    /// Compares the media types of a request body or response, whose vertices the changes to
+    /// the media types themselves lead to
+    fn content(
+        &mut self,
+        location: &str,
+        old: &[Named<Resolved<MediaType>>],
+        new: &[Named<Resolved<MediaType>>],
+        (old_vertex, new_vertex): (&Vertex, &Vertex),
+        direction: Direction,
+    ) {
+        for old_media_type in old {
+            let name = &old_media_type.name;
+            match new.iter().find(|media_type| media_type.name == *name) {
+                Some(new_media_type) => {
+                    if let (Some(old), Some(new)) = (
+                        media_type_schema(&old_media_type.item),
+                        media_type_schema(&new_media_type.item),
+                    ) {
+                        let location = format!("{location} {name}");
+                        self.root_schemas(&location, &old, &new, direction);
+                    }
+                }
+                None => self.push(
+                    "mediaTypeRemoved",
+                    Severity::Breaking,
+                    location,
+                    format!("media type {name} was removed"),
+                    Some(old_vertex.clone()),
+                    Some(new_vertex.clone()),
+                ),
+            }
+        }
+        for new_media_type in new {
+            let name = &new_media_type.name;
+            if !old.iter().any(|media_type| media_type.name == *name) {
+                self.push(
+                    "mediaTypeAdded",
+                    Severity::NonBreaking,
+                    location,
+                    format!("media type {name} was added"),
+                    Some(old_vertex.clone()),
+                    Some(new_vertex.clone()),
+                );
+            }
+        }
+    }
+
+    /// Compares the schemas of a parameter or media type
+    fn root_schemas(
+        &mut self,
+        location: &str,
+        old: &SchemaNode,
+        new: &SchemaNode,
+        direction: Direction,
+    ) {
+        let mut seen = HashSet::new();
+        self.schemas(location, "", old, new, direction, &mut seen);
+    }
+
+    /// Compares two versions of a schema, and the properties and items within them. `property`
+    /// is where the schema is within the root schema, e.g. `items[].id`.
+    ///
+    /// `seen` holds the pairs of schemas that have been compared, so that recursive schemas
+    /// are only compared once.
+    fn schemas(
+        &mut self,
+        location: &str,
+        property: &str,
+        old: &SchemaNode,
+        new: &SchemaNode,
+        direction: Direction,
+        seen: &mut HashSet<(String, String)>,
+    ) {
+        let full_location = match property {
+            "" => location.to_string(),
+            property => format!("{location} {property}"),
+        };
+        // Schemas without a location are told apart by where they are in the root schema
+        let key = |schema: &SchemaNode| {
+            let location = schema.location.clone();
+            location.unwrap_or_else(|| full_location.clone())
+        };
+        if !seen.insert((key(old), key(new))) {
+            return;
+        }
+        let vertices = || {
+            (
+                Some(Vertex::Schema(old.clone())),
+                Some(Vertex::Schema(new.clone())),
+            )
+        };
+        let push = |differ: &mut Self, kind: &'static str, severity: Severity, message: String| {
+            let (old, new) = vertices();
+            differ.push(kind, severity, &full_location, message, old, new);
+        };
+
+        let old_type = old.json.get("type");
+        let new_type = new.json.get("type");
+        if let (Some(old_type), Some(new_type)) = (old_type, new_type) {
+            if old_type != new_type {
+                let message = format!("type changed from {old_type} to {new_type}");
+                push(self, "typeChanged", Severity::Breaking, message);
+                // Nothing else is comparable once the type has changed
+                return;
+            }
+        }
+
+        for (kind, severity, message) in constraint_changes(old, new, direction) {
+            push(self, kind, severity, message);
+        }
+
+        self.properties(location, property, old, new, direction, seen);
+        if let (Some(old_items), Some(new_items)) = (old.json.get("items"), new.json.get("items")) {
+            if let (Some(old_items), Some(new_items)) = (
+                old.child(old_items, &["items"]),
+                new.child(new_items, &["items"]),
+            ) {
+                let property = format!("{property}[]");
+                self.schemas(location, &property, &old_items, &new_items, direction, seen);
+            }
+        }
+    }
+
+    /// Compares the properties of two versions of an object schema
+    fn properties(
+        &mut self,
+        location: &str,
+        property: &str,
+        old: &SchemaNode,
+        new: &SchemaNode,
+        direction: Direction,
+        seen: &mut HashSet<(String, String)>,
+    ) {
+        let empty = Map::new();
+        let properties = |schema: &SchemaNode| {
+            let properties = schema.json.get("properties").and_then(Value::as_object);
+            properties.unwrap_or(&empty).clone()
+        };
+        let old_properties = properties(old);
+        let new_properties = properties(new);
+        let old_required = required(old);
+        let new_required = required(new);
+        let path_of = |name: &str| match property {
+            "" => name.to_string(),
+            property => format!("{property}.{name}"),
+        };
+        let child = |schema: &SchemaNode, value: &Value, name: &str| {
+            schema
+                .child(value, &["properties", name])
+                .map(Vertex::Schema)
+        };
+
+        for (name, old_value) in &old_properties {
+            let property = path_of(name);
+            let full_location = format!("{location} {property}");
+            let old_child = child(old, old_value, name);
+            let Some(new_value) = new_properties.get(name) else {
+                let message = "property was removed".to_string();
+                let severity = direction.widened();
+                self.push(
+                    "propertyRemoved",
+                    severity,
+                    &full_location,
+                    message,
+                    old_child,
+                    None,
+                );
+                continue;
+            };
+            let new_child = child(new, new_value, name);
+            let was_required = old_required.contains(name);
+            let is_required = new_required.contains(name);
+            if was_required != is_required {
+                let (kind, severity, message) = if is_required {
+                    ("propertyBecameRequired", direction.narrowed(), "required")
+                } else {
+                    ("propertyBecameOptional", direction.widened(), "optional")
+                };
+                self.push(
+                    kind,
+                    severity,
+                    &full_location,
+                    format!("property became {message}"),
+                    old_child.clone(),
+                    new_child.clone(),
+                );
+            }
+            if let (Some(Vertex::Schema(old_child)), Some(Vertex::Schema(new_child))) =
+                (old_child, new_child)
+            {
+                self.schemas(location, &property, &old_child, &new_child, direction, seen);
+            }
+        }
+        for (name, new_value) in &new_properties {
+            if old_properties.contains_key(name) {
+                continue;
+            }
+            let (kind, severity, message) = if new_required.contains(name) {
+                (
+                    "requiredPropertyAdded",
+                    direction.narrowed(),
+                    "required property was added",
+                )
+            } else {
+                (
+                    "propertyAdded",
+                    Severity::NonBreaking,
+                    "optional property was added",
+                )
+            };
+            self.push(
+                kind,
+                severity,
+                &format!("{location} {}", path_of(name)),
+                message.to_string(),
+                None,
+                child(new, new_value, name),
+            );
+        }
+    }
+}
+
+/// Changes to what values a schema accepts, other than its type and properties
+fn constraint_changes(
+    old: &SchemaNode,
+    new: &SchemaNode,
+    direction: Direction,
+) -> Vec<(&'static str, Severity, String)> {
+    let narrowed = |message: String| (direction.narrowed_kind(), direction.narrowed(), message);
+    let widened = |message: String| (direction.widened_kind(), direction.widened(), message);
+    let mut constraints = Vec::new();
+    let nullable = |schema: &SchemaNode| schema.json.get("nullable") == Some(&Value::Bool(true));
+    match (nullable(old), nullable(new)) {
+        (true, false) => constraints.push(narrowed("is no longer nullable".to_string())),
+        (false, true) => constraints.push(widened("became nullable".to_string())),
+        _ => {}
+    }
+    for keyword in UPPER_BOUNDS.into_iter().chain(LOWER_BOUNDS) {
+        let is_upper = UPPER_BOUNDS.contains(&keyword);
+        let old_bound = old.json.get(keyword);
+        let new_bound = new.json.get(keyword);
+        let change = match (old_bound, new_bound) {
+            (None, Some(bound)) => narrowed(format!("{keyword} of {bound} was added")),
+            (Some(bound), None) => widened(format!("{keyword} of {bound} was removed")),
+            (Some(old_bound), Some(new_bound)) => {
+                let (Some(from), Some(to)) = (old_bound.as_f64(), new_bound.as_f64()) else {
+                    continue;
+                };
+                let message = format!("{keyword} changed from {old_bound} to {new_bound}");
+                if from == to {
+                    continue;
+                } else if (to < from) == is_upper {
+                    narrowed(message)
+                } else {
+                    widened(message)
+                }
+            }
+            (None, None) => continue,
+        };
+        constraints.push(change);
+    }
+    match (old.json.get("pattern"), new.json.get("pattern")) {
+        (None, Some(pattern)) => constraints.push(narrowed(format!("pattern {pattern} was added"))),
+        (Some(pattern), None) => {
+            constraints.push(widened(format!("pattern {pattern} was removed")))
+        }
+        // There's no telling whether a different pattern matches more or less, so it's
+        // taken to match less
+        (Some(old_pattern), Some(new_pattern)) if old_pattern != new_pattern => {
+            constraints.push(narrowed(format!(
+                "pattern changed from {old_pattern} to {new_pattern}"
+            )))
+        }
+        _ => {}
+    }
+    match (enum_values(old), enum_values(new)) {
+        (None, Some(_)) => constraints.push(narrowed("enum was added".to_string())),
+        (Some(_), None) => constraints.push(widened("enum was removed".to_string())),
+        (Some(old_values), Some(new_values)) => {
+            for value in old_values
+                .iter()
+                .filter(|value| !new_values.contains(value))
+            {
+                let message = format!("enum value {value} was removed");
+                constraints.push(("enumValueRemoved", direction.narrowed(), message));
+            }
+            for value in new_values
+                .iter()
+                .filter(|value| !old_values.contains(value))
+            {
+                let message = format!("enum value {value} was added");
+                constraints.push(("enumValueAdded", direction.widened(), message));
+            }
+        }
+        (None, None) => {}
+    }
+    constraints
+}
+
+fn enum_values(schema: &SchemaNode) -> Option<&Vec<Value>> {
+    schema.json.get("enum").and_then(Value::as_array)
+}
+
+/// Names of an object schema's required properties
+fn required(schema: &SchemaNode) -> Vec<String> {
+    let required = schema.json.get("required").and_then(Value::as_array);
+    required
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+impl<'a> Adapter<'a> for OpenApiDiffAdapter {
+    type Vertex = Vertex;
+
+    fn resolve_starting_vertices(
+        &self,
+        edge_name: &Arc<str>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveInfo,
+    ) -> VertexIterator<'a, Self::Vertex> {
+        match edge_name.as_ref() {
+            "Changes" => Box::new(self.changes.clone().into_iter().map(Vertex::Change)),
+            _ => self
+                .adapter
+                .resolve_starting_vertices(edge_name, parameters, resolve_info),
+        }
+    }
+
+    fn resolve_property<V: AsVertex<Self::Vertex> + 'a>(
+        &self,
+        contexts: ContextIterator<'a, V>,
+        type_name: &Arc<str>,
+        property_name: &Arc<str>,
+        resolve_info: &ResolveInfo,
+    ) -> ContextOutcomeIterator<'a, V, FieldValue> {
+        self.adapter
+            .resolve_property(contexts, type_name, property_name, resolve_info)
+    }
+
+    fn resolve_neighbors<V: AsVertex<Self::Vertex> + 'a>(
+        &self,
+        contexts: ContextIterator<'a, V>,
+        type_name: &Arc<str>,
+        edge_name: &Arc<str>,
+        parameters: &EdgeParameters,
+        resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Self::Vertex>> {
+        self.adapter
+            .resolve_neighbors(contexts, type_name, edge_name, parameters, resolve_info)
+    }
+
+    fn resolve_coercion<V: AsVertex<Self::Vertex> + 'a>(
+        &self,
+        contexts: ContextIterator<'a, V>,
+        type_name: &Arc<str>,
+        coerce_to_type: &Arc<str>,
+        resolve_info: &ResolveInfo,
+    ) -> ContextOutcomeIterator<'a, V, bool> {
+        self.adapter
+            .resolve_coercion(contexts, type_name, coerce_to_type, resolve_info)
+    }
+}
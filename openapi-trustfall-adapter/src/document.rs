@@ -0,0 +1,552 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+};
+
+use indexmap::IndexMap;
+use openapiv3::{
+    Callback, Example, Header, Link, OpenAPI, Parameter, PathItem, ReferenceOr, RequestBody,
+    Response, Schema, SecurityScheme, Tag,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{
+    aws,
+    errors::OpenAPIAdapterErrors,
+    openapi31,
+    provenance::Provenance,
+    refs::{self, escape, Resolved, Target},
+    spans::{Position, Positions},
+    swagger2,
+    utils::{Component, Diagnostic, Named, Operator, Route, SchemaNode},
+};
+
+/// A parsed OpenAPI document, along with the parts of newer spec versions that the
+/// 3.0 model can't hold
+#[derive(Debug, Clone, Default)]
+pub struct Document {
+    pub openapi: OpenAPI,
+    /// The spec version declared by the source document, e.g. `3.1.0`
+    pub version: String,
+    /// Webhooks, added in OpenAPI 3.1
+    pub webhooks: IndexMap<String, ReferenceOr<PathItem>>,
+    /// Reusable path items from `components.pathItems`, added in OpenAPI 3.1
+    pub path_items: IndexMap<String, ReferenceOr<PathItem>>,
+    /// The document as JSON, which local references are resolved against
+    pub source: Value,
+    /// Files that were referenced with a `$ref`, keyed by their path relative to the spec
+    pub external: IndexMap<String, Value>,
+    /// The file the document was loaded from, relative to the directory the specs are in, when
+    /// specs are loaded as separate documents
+    pub source_file: Option<String>,
+    /// Which file each part of the document came from, when it was merged from several
+    pub provenance: Provenance,
+    /// Where each value is in the files the document was loaded from, keyed by file. A document
+    /// that was read from memory has its positions under an empty key
+    pub positions: IndexMap<String, Positions>,
+    /// Schema keywords of a 3.1 document that the 3.0 model can't hold, which are reported as
+    /// diagnostics
+    pub dropped_keywords: Vec<Diagnostic>,
+    /// How many `$ref`s in the document and the files it references point to each location,
+    /// which is counted the first time it's needed
+    reference_counts: OnceLock<HashMap<String, usize>>,
+}
+
+impl Document {
+    /// Parses a YAML or JSON document
+    pub(crate) fn from_str(contents: &str) -> Result<Self, OpenAPIAdapterErrors> {
+        let value: Value = serde_yaml::from_str(contents)
+            .map_err(OpenAPIAdapterErrors::FailedToSerializeToOpenAPI)?;
+        Self::from_value(value)
+    }
+
+    pub(crate) fn from_openapi(openapi: OpenAPI) -> Self {
+        Self {
+            version: openapi.openapi.clone(),
+            source: serde_json::to_value(&openapi).unwrap_or_default(),
+            openapi,
+            ..Default::default()
+        }
+    }
+
+    /// Detects the spec version and reads the document into the 3.0 model
+    pub(crate) fn from_value(mut value: Value) -> Result<Self, OpenAPIAdapterErrors> {
+        if let Some(version) = value.get("swagger").and_then(Value::as_str) {
+            if version != "2.0" {
+                return Err(OpenAPIAdapterErrors::UnsupportedVersion(
+                    version.to_string(),
+                ));
+            }
+            let source = swagger2::convert(value);
+            let openapi = serde_json::from_value(source.clone())
+                .map_err(OpenAPIAdapterErrors::FailedToDeserializeToOpenAPI)?;
+            return Ok(Self {
+                openapi,
+                version: "2.0".to_string(),
+                source,
+                ..Default::default()
+            });
+        }
+        let version = value
+            .get("openapi")
+            .and_then(Value::as_str)
+            .ok_or(OpenAPIAdapterErrors::MissingVersion)?
+            .to_string();
+        let mut webhooks = IndexMap::new();
+        let mut path_items = IndexMap::new();
+        let mut dropped_keywords = Vec::new();
+        if version.starts_with("3.1.") {
+            dropped_keywords = openapi31::dropped_keywords(&value);
+            openapi31::normalize(&mut value);
+        }
+        let source = value.clone();
+        if version.starts_with("3.1.") {
+            webhooks = take(&mut value, "/webhooks")?;
+            path_items = take(&mut value, "/components/pathItems")?;
+        } else if !version.starts_with("3.0.") {
+            return Err(OpenAPIAdapterErrors::UnsupportedVersion(version));
+        }
+        let openapi = serde_json::from_value(value)
+            .map_err(OpenAPIAdapterErrors::FailedToDeserializeToOpenAPI)?;
+        Ok(Self {
+            openapi,
+            version,
+            webhooks,
+            path_items,
+            source,
+            external: IndexMap::new(),
+            source_file: None,
+            provenance: Provenance::default(),
+            positions: IndexMap::new(),
+            dropped_keywords,
+            reference_counts: OnceLock::new(),
+        })
+    }
+
+    /// Follows `$ref`s until an item is reached. References that can't be resolved,
+    /// including ones that loop back on themselves, give `None`, and are reported by
+    /// [`reference_diagnostics`](Self::reference_diagnostics).
+    pub(crate) fn resolve<T: DeserializeOwned + Clone>(
+        self: &Arc<Self>,
+        item: &ReferenceOr<T>,
+    ) -> Option<Resolved<T>> {
+        self.resolve_with(item, |_| {})
+    }
+
+    /// Resolves a schema. Schemas in files referenced from a 3.1 document are normalized
+    /// here, since it's only known that they hold a schema once they're referenced as one.
+    pub(crate) fn resolve_schema(
+        self: &Arc<Self>,
+        item: &ReferenceOr<Schema>,
+    ) -> Option<Resolved<Schema>> {
+        if self.version.starts_with("3.1.") {
+            self.resolve_with(item, openapi31::normalize_schema)
+        } else {
+            self.resolve(item)
+        }
+    }
+
+    /// The schemas defined in `components.schemas`, by name
+    pub(crate) fn schemas(self: &Arc<Self>) -> Vec<SchemaNode> {
+        self.openapi
+            .components
+            .iter()
+            .flat_map(|components| components.schemas.iter())
+            .filter_map(|(name, schema)| {
+                let location = format!("#/components/schemas/{}", escape(name));
+                let schema = self.resolve_schema(schema)?.at(Some(location));
+                Some(SchemaNode::new(Some(name.clone()), schema))
+            })
+            .collect()
+    }
+
+    /// The tags declared at the document level
+    pub(crate) fn tags(self: &Arc<Self>) -> Vec<Resolved<Tag>> {
+        self.openapi
+            .tags
+            .iter()
+            .map(|tag| Resolved {
+                item: tag.clone(),
+                reference: None,
+                document: self.clone(),
+                location: Some(format!("#/tags/{}", escape(&tag.name))),
+            })
+            .collect()
+    }
+
+    /// The security schemes defined in `components.securitySchemes`, by name
+    pub(crate) fn security_schemes(self: &Arc<Self>) -> Vec<Named<Resolved<SecurityScheme>>> {
+        self.openapi
+            .components
+            .iter()
+            .flat_map(|components| components.security_schemes.iter())
+            .filter_map(|(name, scheme)| {
+                let location = format!("#/components/securitySchemes/{}", escape(name));
+                Some(Named {
+                    name: name.clone(),
+                    item: self.resolve(scheme)?.at(Some(location)),
+                })
+            })
+            .collect()
+    }
+
+    /// Every entry of the `components` sections, e.g. each parameter in `components.parameters`
+    pub(crate) fn components(self: &Arc<Self>) -> Vec<Component> {
+        let sections = self.source.get("components").and_then(Value::as_object);
+        sections
+            .into_iter()
+            .flatten()
+            .filter(|(kind, _)| !kind.starts_with("x-"))
+            .filter_map(|(kind, section)| Some((kind, section.as_object()?)))
+            .flat_map(|(kind, section)| {
+                section.keys().map(move |name| Component {
+                    kind: kind.clone(),
+                    name: name.clone(),
+                    document: self.clone(),
+                    location: Some(format!("#/components/{kind}/{}", escape(name))),
+                })
+            })
+            .collect()
+    }
+
+    /// How many security requirements, of the document or of its operations, use the scheme
+    pub(crate) fn security_requirement_count(&self, scheme: &str) -> usize {
+        let path_items = ["/paths", "/webhooks"]
+            .into_iter()
+            .filter_map(|pointer| self.source.pointer(pointer)?.as_object())
+            .flat_map(|items| items.values())
+            .map(|item| match item.get("$ref").and_then(Value::as_str) {
+                Some(reference) => self.lookup(reference).unwrap_or(item),
+                None => item,
+            });
+        let operations = path_items
+            .filter_map(Value::as_object)
+            .flat_map(|item| item.values());
+        std::iter::once(&self.source)
+            .chain(operations)
+            .filter_map(|value| value.get("security")?.as_array())
+            .flatten()
+            .filter(|requirement| requirement.get(scheme).is_some())
+            .count()
+    }
+
+    fn resolve_with<T: DeserializeOwned + Clone>(
+        self: &Arc<Self>,
+        item: &ReferenceOr<T>,
+        normalize: impl Fn(&mut Value),
+    ) -> Option<Resolved<T>> {
+        self.try_resolve(item, normalize).ok()
+    }
+
+    /// Follows `$ref`s until an item is reached, giving why it couldn't be if it isn't
+    fn try_resolve<T: DeserializeOwned + Clone>(
+        self: &Arc<Self>,
+        item: &ReferenceOr<T>,
+        normalize: impl Fn(&mut Value),
+    ) -> Result<Resolved<T>, Diagnostic> {
+        let first = match item {
+            ReferenceOr::Item(item) => {
+                return Ok(Resolved {
+                    item: item.clone(),
+                    reference: None,
+                    document: self.clone(),
+                    location: None,
+                })
+            }
+            ReferenceOr::Reference { reference } => reference,
+        };
+        let mut followed: Vec<&str> = Vec::new();
+        let mut reference = first.as_str();
+        loop {
+            let diagnostic = |kind: &str, message: String| Diagnostic {
+                kind: kind.to_string(),
+                name: first.clone(),
+                message,
+            };
+            if followed.contains(&reference) {
+                let cycle = followed.join(" -> ");
+                let message = format!("Cyclic reference: {cycle} -> {reference}");
+                return Err(diagnostic("cyclic-reference", message));
+            }
+            followed.push(reference);
+            let Some(value) = self.lookup(reference) else {
+                let message = format!("Failed to resolve reference: {reference}");
+                return Err(diagnostic("unresolved-reference", message));
+            };
+            if let Some(next) = value.get("$ref").and_then(Value::as_str) {
+                reference = next;
+                continue;
+            }
+            let mut value = value.clone();
+            normalize(&mut value);
+            return match serde_json::from_value(value) {
+                Ok(item) => Ok(Resolved {
+                    item,
+                    reference: Some(first.clone()),
+                    document: self.clone(),
+                    location: Some(reference.to_string()),
+                }),
+                Err(e) => {
+                    let message = format!("Failed to deserialize reference {reference}: {e}");
+                    Err(diagnostic("invalid-reference", message))
+                }
+            };
+        }
+    }
+
+    /// Problems with the document that don't stop it from being queried: `$ref`s that can't be
+    /// resolved, AWS extensions that can't be read and 3.1 schema keywords that are dropped
+    pub(crate) fn diagnostics(self: &Arc<Self>) -> Vec<Diagnostic> {
+        let mut diagnostics = self.reference_diagnostics();
+        if self.version.starts_with("3.1.") {
+            diagnostics.extend(self.dropped_keyword_diagnostics());
+        }
+        aws::extension_diagnostics(&self.source, "#", &mut diagnostics);
+        for (file, value) in &self.external {
+            aws::extension_diagnostics(value, &format!("{file}#"), &mut diagnostics);
+        }
+        diagnostics
+    }
+
+    /// The `$ref`s in the document and the files it references that can't be resolved, loop
+    /// back on themselves, or point to something that isn't what they're used as, e.g. a
+    /// parameter that points to a schema. Each reference is reported once
+    fn reference_diagnostics(self: &Arc<Self>) -> Vec<Diagnostic> {
+        let mut references = Vec::new();
+        refs::find_references(&self.source, String::new(), &mut references);
+        for value in self.external.values() {
+            refs::find_references(value, String::new(), &mut references);
+        }
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        for (pointer, reference) in references {
+            let Some(diagnostic) = self.check_reference(&pointer, reference) else {
+                continue;
+            };
+            if !diagnostics
+                .iter()
+                .any(|found| found.name == diagnostic.name)
+            {
+                diagnostics.push(diagnostic);
+            }
+        }
+        diagnostics
+    }
+
+    /// The schema keywords that are dropped from a 3.1 document, and from the schemas it
+    /// references in other files, which are only normalized when they're resolved
+    fn dropped_keyword_diagnostics(&self) -> Vec<Diagnostic> {
+        let mut references = Vec::new();
+        refs::find_references(&self.source, String::new(), &mut references);
+        for value in self.external.values() {
+            refs::find_references(value, String::new(), &mut references);
+        }
+        let mut external = Vec::new();
+        for (pointer, reference) in references {
+            let (file, _) = refs::split(&reference);
+            if file.is_empty() || refs::reference_target(&pointer) != Some(Target::Schema) {
+                continue;
+            }
+            if let Some(schema) = self.lookup(&reference) {
+                openapi31::schema_dropped_keywords(schema, &reference, &mut external);
+            }
+        }
+        let mut diagnostics = self.dropped_keywords.clone();
+        for diagnostic in external {
+            if !diagnostics
+                .iter()
+                .any(|found| found.name == diagnostic.name)
+            {
+                diagnostics.push(diagnostic);
+            }
+        }
+        diagnostics
+    }
+
+    /// Why the reference at the pointer can't be resolved as what it's used as, if it can't.
+    /// A reference that isn't used as anything known is only checked to lead somewhere
+    fn check_reference(self: &Arc<Self>, pointer: &str, reference: String) -> Option<Diagnostic> {
+        fn check<T: DeserializeOwned + Clone>(
+            document: &Arc<Document>,
+            reference: String,
+            normalize: impl Fn(&mut Value),
+        ) -> Option<Diagnostic> {
+            let item = ReferenceOr::<T>::Reference { reference };
+            document.try_resolve(&item, normalize).err()
+        }
+        let unchanged = |_: &mut Value| {};
+        match refs::reference_target(pointer) {
+            Some(Target::Callback) => check::<Callback>(self, reference, unchanged),
+            Some(Target::Example) => check::<Example>(self, reference, unchanged),
+            Some(Target::Header) => check::<Header>(self, reference, unchanged),
+            Some(Target::Link) => check::<Link>(self, reference, unchanged),
+            Some(Target::Parameter) => check::<Parameter>(self, reference, unchanged),
+            Some(Target::PathItem) => check::<PathItem>(self, reference, unchanged),
+            Some(Target::RequestBody) => check::<RequestBody>(self, reference, unchanged),
+            Some(Target::Response) => check::<Response>(self, reference, unchanged),
+            Some(Target::Schema) if self.version.starts_with("3.1.") => {
+                check::<Schema>(self, reference, openapi31::normalize_schema)
+            }
+            Some(Target::Schema) => check::<Schema>(self, reference, unchanged),
+            Some(Target::SecurityScheme) => check::<SecurityScheme>(self, reference, unchanged),
+            None => check::<Value>(self, reference, unchanged),
+        }
+    }
+
+    /// The JSON value that a reference points to
+    fn lookup(&self, reference: &str) -> Option<&Value> {
+        let (file, pointer) = refs::split(reference);
+        let source = if file.is_empty() {
+            &self.source
+        } else {
+            self.external.get(file)?
+        };
+        source.pointer(pointer)
+    }
+
+    /// The file that the item at a location, e.g. `#/paths/~1users/get`, was loaded from
+    pub(crate) fn source_file_of(&self, location: &str) -> Option<String> {
+        let (file, pointer) = refs::split(location);
+        if !file.is_empty() {
+            return Some(file.to_string());
+        }
+        self.source_file
+            .clone()
+            .or_else(|| self.provenance.source_file(pointer).map(str::to_string))
+    }
+
+    /// Where the item at a location is in the file it was loaded from
+    pub(crate) fn position_of(&self, location: &str) -> Option<Position> {
+        let file = self.source_file_of(location).unwrap_or_default();
+        let (_, pointer) = refs::split(location);
+        self.positions.get(&file)?.get(pointer).copied()
+    }
+
+    /// How many `$ref`s in the document and the files it references point to the location,
+    /// e.g. `#/components/schemas/User`
+    pub(crate) fn reference_count(&self, location: &str) -> usize {
+        let counts = self.reference_counts.get_or_init(|| {
+            let mut references = HashMap::new();
+            refs::count_references(&self.source, &mut references);
+            for (file, value) in &self.external {
+                // A file of the document that another file references back into is loaded
+                // again, but its references are already counted
+                if !self.is_own_file(file) {
+                    refs::count_references(value, &mut references);
+                }
+            }
+            let mut counts = HashMap::new();
+            for (reference, count) in references {
+                *counts.entry(self.local_reference(reference)).or_default() += count;
+            }
+            counts
+        });
+        counts.get(location).copied().unwrap_or_default()
+    }
+
+    /// Whether the file, keyed by its path relative to the spec, is one the document was loaded
+    /// from rather than one it references
+    fn is_own_file(&self, file: &str) -> bool {
+        self.source_file.as_deref() == Some(file) || self.provenance.contains_file(file)
+    }
+
+    /// A reference as a local one when it points into the document from another file, e.g.
+    /// `openapi.yaml#/components/schemas/User` becomes `#/components/schemas/User`
+    fn local_reference(&self, reference: String) -> String {
+        match refs::split(&reference) {
+            (file, pointer) if self.is_own_file(file) => format!("#{pointer}"),
+            _ => reference,
+        }
+    }
+
+    /// Builds the route for a path item, following references
+    pub(crate) fn route(self: &Arc<Self>, path: &str, item: &ReferenceOr<PathItem>) -> Route {
+        self.route_at(path, item, format!("#/paths/{}", escape(path)))
+    }
+
+    /// Builds the route for a path item at the location, following references
+    fn route_at(
+        self: &Arc<Self>,
+        path: &str,
+        item: &ReferenceOr<PathItem>,
+        location: String,
+    ) -> Route {
+        match self.resolve(item) {
+            Some(resolved) => Route::from_path_item(
+                path,
+                &resolved.item,
+                resolved.reference,
+                Some(location),
+                self,
+            ),
+            None => Route {
+                unresolved: true,
+                ..Route::from_path_item(
+                    path,
+                    &PathItem::default(),
+                    reference_of(item),
+                    Some(location),
+                    self,
+                )
+            },
+        }
+    }
+
+    pub(crate) fn routes(self: &Arc<Self>) -> Vec<Route> {
+        self.openapi
+            .paths
+            .iter()
+            .map(|(path, item)| self.route(path, item))
+            .collect()
+    }
+
+    /// The operation with the given `operationId`
+    pub(crate) fn operation_by_id(self: &Arc<Self>, operation_id: &str) -> Option<Operator> {
+        self.routes().iter().find_map(|route| {
+            route
+                .operations()
+                .into_iter()
+                .find(|operation| operation.operation.operation_id.as_deref() == Some(operation_id))
+                .cloned()
+        })
+    }
+
+    /// The operation a local `operationRef` points to, e.g. `#/paths/~1users~1{id}/get`
+    pub(crate) fn operation_by_ref(self: &Arc<Self>, reference: &str) -> Option<Operator> {
+        let (path, method) = reference.strip_prefix("#/paths/")?.rsplit_once('/')?;
+        let path = path.replace("~1", "/").replace("~0", "~");
+        let item = self.openapi.paths.paths.get(&path)?;
+        self.route(&path, item)
+            .operations()
+            .into_iter()
+            .find(|operation| operation.method.eq_ignore_ascii_case(method))
+            .cloned()
+    }
+
+    /// Webhooks as routes, with the webhook name as the path
+    pub(crate) fn webhook_routes(self: &Arc<Self>) -> Vec<Route> {
+        self.webhooks
+            .iter()
+            .map(|(name, item)| self.route_at(name, item, format!("#/webhooks/{}", escape(name))))
+            .collect()
+    }
+}
+
+fn reference_of<T>(item: &ReferenceOr<T>) -> Option<String> {
+    match item {
+        ReferenceOr::Reference { reference } => Some(reference.clone()),
+        ReferenceOr::Item(_) => None,
+    }
+}
+
+/// Removes and deserializes the value at the pointer, defaulting if it isn't there
+fn take<T: serde::de::DeserializeOwned + Default>(
+    value: &mut Value,
+    pointer: &str,
+) -> Result<T, OpenAPIAdapterErrors> {
+    match value.pointer_mut(pointer).map(Value::take) {
+        Some(taken) if !taken.is_null() => serde_json::from_value(taken)
+            .map_err(OpenAPIAdapterErrors::FailedToDeserializeToOpenAPI),
+        _ => Ok(T::default()),
+    }
+}
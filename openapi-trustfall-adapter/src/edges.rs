@@ -1,212 +1,2079 @@
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+use openapiv3::{MediaType, SecurityRequirement};
+use serde_json::Value;
 use trustfall::provider::{
-    AsVertex, ContextIterator, ContextOutcomeIterator, EdgeParameters, ResolveEdgeInfo,
-    VertexIterator,
+    resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, EdgeParameters,
+    ResolveEdgeInfo, VertexIterator,
+};
+
+use crate::{
+    document::Document,
+    refs::Resolved,
+    utils::{media_types, Named},
 };
 
 use super::vertex::Vertex;
 
-pub(super) fn resolve_operation_edge<'a, V: AsVertex<Vertex> + 'a>(
+/// Parameter mappings or mapping templates of an AWS integration, integration response or
+/// gateway response
+fn mappings<'a>(mappings: &IndexMap<String, String>) -> VertexIterator<'a, Vertex> {
+    let mappings: Vec<Vertex> = mappings
+        .iter()
+        .map(|(key, value)| {
+            Vertex::AmazonApigatewayMapping(Named {
+                name: key.clone(),
+                item: value.clone(),
+            })
+        })
+        .collect();
+    Box::new(mappings.into_iter())
+}
+
+/// Media types of a request body or response's content
+fn content<'a>(
+    content: &IndexMap<String, MediaType>,
+    location: Option<&str>,
+    document: &Arc<Document>,
+) -> VertexIterator<'a, Vertex> {
+    let media_types = media_types(content, location, document);
+    Box::new(media_types.into_iter().map(Vertex::MediaType))
+}
+
+/// Vendor extensions (`x-` keys) of any vertex that can have them
+fn extensions<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    _resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    resolve_neighbors_with(contexts, move |vertex| {
+        let extensions: Vec<(&String, &Value)> = match vertex {
+            Vertex::Document(document) => document.openapi.extensions.iter().collect(),
+            Vertex::Info(info) => info.extensions.iter().collect(),
+            Vertex::Path(route) => route.extensions.iter().collect(),
+            Vertex::Operation(operation) => operation.operation.extensions.iter().collect(),
+            Vertex::Parameter(parameter) => parameter
+                .item
+                .parameter_data_ref()
+                .extensions
+                .iter()
+                .collect(),
+            Vertex::Response(response) => response.item.item.extensions.iter().collect(),
+            Vertex::Schema(schema) => schema
+                .json
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter(|(key, _)| key.starts_with("x-"))
+                .collect(),
+            _ => unreachable!("attempted to resolve 'extensions' on vertex {vertex:?}"),
+        };
+        let extensions: Vec<Vertex> = extensions
+            .into_iter()
+            .map(|(name, value)| {
+                Vertex::Extension(Named {
+                    name: name.clone(),
+                    item: value.clone(),
+                })
+            })
+            .collect();
+        Box::new(extensions.into_iter())
+    })
+}
+
+/// Servers of a path or operation
+fn servers<'a>(servers: Vec<openapiv3::Server>) -> VertexIterator<'a, Vertex> {
+    Box::new(servers.into_iter().map(Vertex::Server))
+}
+
+/// Security requirements of a document or operation
+fn security_requirements<'a>(
+    requirements: Vec<SecurityRequirement>,
+    document: &Arc<Document>,
+) -> VertexIterator<'a, Vertex> {
+    let document = document.clone();
+    Box::new(requirements.into_iter().map(move |requirement| {
+        Vertex::SecurityRequirement(Resolved {
+            item: requirement,
+            reference: None,
+            document: document.clone(),
+            location: None,
+        })
+    }))
+}
+
+pub(super) fn resolve_amazon_apigateway_authorizer_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
     resolve_info: &ResolveEdgeInfo,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "xAmazonApigatewayIntegration" => {
-            operation::x_amazon_apigateway_integration(contexts, resolve_info)
-        }
+        "uriArn" => amazon_apigateway_authorizer::uri_arn(contexts, resolve_info),
         _ => {
-            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Operation'")
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'AmazonApigatewayAuthorizer'"
+            )
         }
     }
 }
 
-mod operation {
+mod amazon_apigateway_authorizer {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn uri_arn<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let authorizer = vertex
+                .as_amazon_apigateway_authorizer()
+                .expect("conversion failed, vertex was not a AmazonApigatewayAuthorizer");
+            Box::new(
+                authorizer
+                    .uri_arn
+                    .clone()
+                    .into_iter()
+                    .map(Vertex::AmazonApigatewayIntegrationArn),
+            )
+        })
+    }
+}
+
+pub(super) fn resolve_amazon_apigateway_gateway_response_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "responseParameters" => {
+            amazon_apigateway_gateway_response::response_parameters(contexts, resolve_info)
+        }
+        "responseTemplates" => {
+            amazon_apigateway_gateway_response::response_templates(contexts, resolve_info)
+        }
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'AmazonApigatewayGatewayResponse'"
+            )
+        }
+    }
+}
 
+mod amazon_apigateway_gateway_response {
     use trustfall::provider::{
         resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
         VertexIterator,
     };
 
-    use crate::utils::Operator;
+    use crate::{aws::GatewayResponse, utils::Named};
 
     use super::super::vertex::Vertex;
 
-    pub(super) fn x_amazon_apigateway_integration<'a, V: AsVertex<Vertex> + 'a>(
+    fn response(vertex: &Vertex) -> &Named<GatewayResponse> {
+        vertex
+            .as_amazon_apigateway_gateway_response()
+            .expect("conversion failed, vertex was not a AmazonApigatewayGatewayResponse")
+    }
+
+    pub(super) fn response_parameters<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
         resolve_neighbors_with(contexts, move |vertex| {
-            let operation: &Operator = vertex
-                .as_operation()
-                .expect("conversion failed, vertex was not a Operation");
+            super::mappings(&response(vertex).item.response_parameters)
+        })
+    }
 
-            match &operation.aws {
-                Some(value) => Box::new(std::iter::once(Vertex::AmazonApigatewayIntegration(
-                    value.clone(),
-                ))),
-                None => Box::new(std::iter::empty()),
-            }
+    pub(super) fn response_templates<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            super::mappings(&response(vertex).item.response_templates)
         })
     }
 }
 
-pub(super) fn resolve_path_edge<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_amazon_apigateway_integration_edge<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     edge_name: &str,
     _parameters: &EdgeParameters,
     resolve_info: &ResolveEdgeInfo,
 ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
     match edge_name {
-        "delete" => path::delete(contexts, resolve_info),
-        "get" => path::get(contexts, resolve_info),
-        "operations" => path::operations(contexts, resolve_info),
-        "options" => path::options(contexts, resolve_info),
-        "patch" => path::patch(contexts, resolve_info),
-        "post" => path::post(contexts, resolve_info),
-        "put" => path::put(contexts, resolve_info),
+        "requestParameters" => {
+            amazon_apigateway_integration::request_parameters(contexts, resolve_info)
+        }
+        "requestTemplates" => {
+            amazon_apigateway_integration::request_templates(contexts, resolve_info)
+        }
+        "responses" => amazon_apigateway_integration::responses(contexts, resolve_info),
+        "uriArn" => amazon_apigateway_integration::uri_arn(contexts, resolve_info),
         _ => {
-            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Path'")
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'AmazonApigatewayIntegration'"
+            )
         }
     }
 }
 
-mod path {
-
+mod amazon_apigateway_integration {
     use trustfall::provider::{
         resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
         VertexIterator,
     };
 
-    use crate::utils::Route;
+    use crate::{aws::AmazonApigatewayIntegration, utils::Named};
 
     use super::super::vertex::Vertex;
 
-    pub(super) fn delete<'a, V: AsVertex<Vertex> + 'a>(
+    fn integration(vertex: &Vertex) -> &AmazonApigatewayIntegration {
+        vertex
+            .as_amazon_apigateway_integration()
+            .expect("conversion failed, vertex was not a AmazonApigatewayIntegration")
+    }
+
+    pub(super) fn request_parameters<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
         resolve_neighbors_with(contexts, move |vertex| {
-            let route: &Route = vertex
-                .as_path()
-                .expect("conversion failed, vertex was not a Path");
-
-            match &route.delete {
-                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
-                None => Box::new(std::iter::empty()),
-            }
+            super::mappings(&integration(vertex).request_parameters)
         })
     }
 
-    pub(super) fn get<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn request_templates<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
         resolve_neighbors_with(contexts, move |vertex| {
-            let route: &Route = vertex
-                .as_path()
-                .expect("conversion failed, vertex was not a Path");
-
-            match &route.get {
-                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
-                None => Box::new(std::iter::empty()),
-            }
+            super::mappings(&integration(vertex).request_templates)
         })
     }
 
-    pub(super) fn operations<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn responses<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
         resolve_neighbors_with(contexts, move |vertex| {
-            let route: &Route = vertex
-                .as_path()
-                .expect("conversion failed, vertex was not a Path");
-
-            let mut operations = Vec::new();
-            if let Some(op) = &route.delete {
-                operations.push(Vertex::Operation(op.clone()));
-            }
-            if let Some(op) = &route.get {
-                operations.push(Vertex::Operation(op.clone()));
-            }
-            if let Some(op) = &route.options {
-                operations.push(Vertex::Operation(op.clone()));
-            }
-            if let Some(op) = &route.patch {
-                operations.push(Vertex::Operation(op.clone()));
-            }
-            if let Some(op) = &route.post {
-                operations.push(Vertex::Operation(op.clone()));
-            }
-            if let Some(op) = &route.put {
-                operations.push(Vertex::Operation(op.clone()));
-            }
-            Box::new(operations.into_iter())
+            let responses: Vec<Vertex> = integration(vertex)
+                .responses
+                .iter()
+                .map(|(pattern, response)| {
+                    Vertex::AmazonApigatewayIntegrationResponse(Named {
+                        name: pattern.clone(),
+                        item: response.clone(),
+                    })
+                })
+                .collect();
+            Box::new(responses.into_iter())
         })
     }
 
-    pub(super) fn options<'a, V: AsVertex<Vertex> + 'a>(
+    pub(super) fn uri_arn<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
         resolve_neighbors_with(contexts, move |vertex| {
-            let route: &Route = vertex
-                .as_path()
-                .expect("conversion failed, vertex was not a Path");
-
-            match &route.options {
-                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
-                None => Box::new(std::iter::empty()),
-            }
+            Box::new(
+                integration(vertex)
+                    .uri_arn
+                    .clone()
+                    .into_iter()
+                    .map(Vertex::AmazonApigatewayIntegrationArn),
+            )
         })
     }
+}
 
-    pub(super) fn patch<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_amazon_apigateway_integration_response_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "responseParameters" => {
+            amazon_apigateway_integration_response::response_parameters(contexts, resolve_info)
+        }
+        "responseTemplates" => {
+            amazon_apigateway_integration_response::response_templates(contexts, resolve_info)
+        }
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'AmazonApigatewayIntegrationResponse'"
+            )
+        }
+    }
+}
+
+mod amazon_apigateway_integration_response {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::{aws::IntegrationResponse, utils::Named};
+
+    use super::super::vertex::Vertex;
+
+    fn response(vertex: &Vertex) -> &Named<IntegrationResponse> {
+        vertex
+            .as_amazon_apigateway_integration_response()
+            .expect("conversion failed, vertex was not a AmazonApigatewayIntegrationResponse")
+    }
+
+    pub(super) fn response_parameters<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
         resolve_neighbors_with(contexts, move |vertex| {
-            let route: &Route = vertex
-                .as_path()
-                .expect("conversion failed, vertex was not a Path");
+            super::mappings(&response(vertex).item.response_parameters)
+        })
+    }
 
-            match &route.patch {
-                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
-                None => Box::new(std::iter::empty()),
-            }
+    pub(super) fn response_templates<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            super::mappings(&response(vertex).item.response_templates)
         })
     }
+}
+
+pub(super) fn resolve_callback_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "path" => callback::path(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Callback'")
+        }
+    }
+}
 
-    pub(super) fn post<'a, V: AsVertex<Vertex> + 'a>(
+mod callback {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn path<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
         resolve_neighbors_with(contexts, move |vertex| {
-            let route: &Route = vertex
-                .as_path()
-                .expect("conversion failed, vertex was not a Path");
+            let callback = vertex
+                .as_callback()
+                .expect("conversion failed, vertex was not a Callback");
+            Box::new(std::iter::once(Vertex::Path(callback.item.clone())))
+        })
+    }
+}
 
-            match &route.post {
-                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
-                None => Box::new(std::iter::empty()),
-            }
+pub(super) fn resolve_change_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "new" => change::vertex(contexts, false, resolve_info),
+        "old" => change::vertex(contexts, true, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Change'")
+        }
+    }
+}
+
+mod change {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use super::super::vertex::Vertex;
+
+    /// The changed item in the old spec, or else in the new one
+    pub(super) fn vertex<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        is_old: bool,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let change = vertex
+                .as_change()
+                .expect("conversion failed, vertex was not a Change");
+            let vertex = if is_old { &change.old } else { &change.new };
+            Box::new(vertex.as_deref().cloned().into_iter())
         })
     }
+}
 
-    pub(super) fn put<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_extension_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "value" => extension::value(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Extension'")
+        }
+    }
+}
+
+mod extension {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn value<'a, V: AsVertex<Vertex> + 'a>(
         contexts: ContextIterator<'a, V>,
         _resolve_info: &ResolveEdgeInfo,
     ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
         resolve_neighbors_with(contexts, move |vertex| {
-            let route: &Route = vertex
-                .as_path()
-                .expect("conversion failed, vertex was not a Path");
+            let extension = vertex
+                .as_extension()
+                .expect("conversion failed, vertex was not a Extension");
+            Box::new(std::iter::once(Vertex::JsonValue(extension.clone())))
+        })
+    }
+}
 
-            match &route.put {
-                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
-                None => Box::new(std::iter::empty()),
-            }
+pub(super) fn resolve_document_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "extensions" => extensions(contexts, resolve_info),
+        "info" => document::info(contexts, resolve_info),
+        "paths" => document::paths(contexts, resolve_info),
+        "schemas" => document::schemas(contexts, resolve_info),
+        "security" => document::security(contexts, resolve_info),
+        "securitySchemes" => document::security_schemes(contexts, resolve_info),
+        "tags" => document::tags(contexts, resolve_info),
+        "webhooks" => document::webhooks(contexts, resolve_info),
+        "xAmazonApigatewayCors" => document::x_amazon_apigateway_cors(contexts, resolve_info),
+        "xAmazonApigatewayGatewayResponses" => {
+            document::x_amazon_apigateway_gateway_responses(contexts, resolve_info)
+        }
+        "xAmazonApigatewayRequestValidator" => {
+            document::x_amazon_apigateway_request_validator(contexts, resolve_info)
+        }
+        "xAmazonApigatewayRequestValidators" => {
+            document::x_amazon_apigateway_request_validators(contexts, resolve_info)
+        }
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Document'")
+        }
+    }
+}
+
+mod document {
+    use std::sync::Arc;
+
+    use indexmap::IndexMap;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::{
+        aws::{self, AmazonApigatewayCors, GatewayResponse},
+        document::Document,
+        utils::Named,
+    };
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn info<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let document: &Arc<Document> = vertex
+                .as_document()
+                .expect("conversion failed, vertex was not a Document");
+            Box::new(std::iter::once(Vertex::Info(document.openapi.info.clone())))
+        })
+    }
+
+    pub(super) fn paths<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let document: &Arc<Document> = vertex
+                .as_document()
+                .expect("conversion failed, vertex was not a Document");
+            Box::new(
+                document
+                    .routes()
+                    .into_iter()
+                    .map(|route| Vertex::Path(Box::new(route))),
+            )
+        })
+    }
+
+    pub(super) fn schemas<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let document: &Arc<Document> = vertex
+                .as_document()
+                .expect("conversion failed, vertex was not a Document");
+            Box::new(document.schemas().into_iter().map(Vertex::Schema))
+        })
+    }
+
+    pub(super) fn security<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let document: &Arc<Document> = vertex
+                .as_document()
+                .expect("conversion failed, vertex was not a Document");
+            let requirements = document.openapi.security.clone().unwrap_or_default();
+            super::security_requirements(requirements, document)
+        })
+    }
+
+    pub(super) fn security_schemes<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let document: &Arc<Document> = vertex
+                .as_document()
+                .expect("conversion failed, vertex was not a Document");
+            Box::new(
+                document
+                    .security_schemes()
+                    .into_iter()
+                    .map(Vertex::security_scheme),
+            )
+        })
+    }
+
+    pub(super) fn tags<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let document: &Arc<Document> = vertex
+                .as_document()
+                .expect("conversion failed, vertex was not a Document");
+            Box::new(document.tags().into_iter().map(Vertex::Tag))
+        })
+    }
+
+    pub(super) fn webhooks<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let document: &Arc<Document> = vertex
+                .as_document()
+                .expect("conversion failed, vertex was not a Document");
+            Box::new(
+                document
+                    .webhook_routes()
+                    .into_iter()
+                    .map(|route| Vertex::Path(Box::new(route))),
+            )
+        })
+    }
+
+    pub(super) fn x_amazon_apigateway_cors<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let document: &Arc<Document> = vertex
+                .as_document()
+                .expect("conversion failed, vertex was not a Document");
+            let cors: Option<AmazonApigatewayCors> =
+                aws::extension(&document.openapi.extensions, "x-amazon-apigateway-cors");
+            Box::new(cors.into_iter().map(Vertex::AmazonApigatewayCors))
+        })
+    }
+
+    pub(super) fn x_amazon_apigateway_gateway_responses<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let document: &Arc<Document> = vertex
+                .as_document()
+                .expect("conversion failed, vertex was not a Document");
+            let responses: IndexMap<String, GatewayResponse> = aws::extension(
+                &document.openapi.extensions,
+                "x-amazon-apigateway-gateway-responses",
+            )
+            .unwrap_or_default();
+            Box::new(
+                responses.into_iter().map(|(name, item)| {
+                    Vertex::AmazonApigatewayGatewayResponse(Named { name, item })
+                }),
+            )
+        })
+    }
+
+    pub(super) fn x_amazon_apigateway_request_validator<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let document: &Arc<Document> = vertex
+                .as_document()
+                .expect("conversion failed, vertex was not a Document");
+            let extensions = &document.openapi.extensions;
+            Box::new(
+                aws::request_validator(extensions, extensions)
+                    .into_iter()
+                    .map(Vertex::AmazonApigatewayRequestValidator),
+            )
+        })
+    }
+
+    pub(super) fn x_amazon_apigateway_request_validators<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let document: &Arc<Document> = vertex
+                .as_document()
+                .expect("conversion failed, vertex was not a Document");
+            Box::new(
+                aws::request_validators(&document.openapi.extensions)
+                    .into_iter()
+                    .map(Vertex::AmazonApigatewayRequestValidator),
+            )
+        })
+    }
+}
+
+pub(super) fn resolve_example_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "validationErrors" => example::validation_errors(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Example'")
+        }
+    }
+}
+
+mod example {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::validation::validate_example;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn validation_errors<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let example = vertex
+                .as_example()
+                .expect("conversion failed, vertex was not a Example");
+            let errors = validate_example(example);
+            Box::new(errors.into_iter().map(Vertex::ValidationError))
+        })
+    }
+}
+
+pub(super) fn resolve_header_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "schema" => header::schema(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Header'")
+        }
+    }
+}
+
+mod header {
+    use openapiv3::Header;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::{
+        refs::{join, Resolved},
+        utils::{schema_or_content, Named, SchemaNode},
+    };
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn schema<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let header: &Named<Resolved<Header>> = vertex
+                .as_header()
+                .expect("conversion failed, vertex was not a Header");
+            let schema = schema_or_content(&header.item.item.format).and_then(|(schema, keys)| {
+                let location = join(header.item.location.as_deref(), &keys);
+                Some(header.item.document.resolve_schema(schema)?.at(location))
+            });
+            match schema {
+                Some(schema) => Box::new(std::iter::once(Vertex::Schema(SchemaNode::new(
+                    None, schema,
+                )))),
+                None => Box::new(std::iter::empty()),
+            }
+        })
+    }
+}
+
+pub(super) fn resolve_info_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "extensions" => extensions(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Info'")
+        }
+    }
+}
+
+pub(super) fn resolve_json_value_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "entries" => json_value::entries(contexts, resolve_info),
+        "field" => {
+            let name: &str = parameters
+                .get("name")
+                .expect("failed to find parameter 'name' for edge 'field' on type 'JsonValue'")
+                .as_str()
+                .expect("unexpected null or other incorrect datatype for Trustfall type 'String!'");
+            json_value::field(contexts, name, resolve_info)
+        }
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'JsonValue'")
+        }
+    }
+}
+
+mod json_value {
+    use serde_json::Value;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::utils::Named;
+
+    use super::super::vertex::Vertex;
+
+    fn json_value(vertex: &Vertex) -> &Named<Value> {
+        vertex
+            .as_json_value()
+            .expect("conversion failed, vertex was not a JsonValue")
+    }
+
+    pub(super) fn entries<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let entries: Vec<Vertex> = match &json_value(vertex).item {
+                Value::Object(object) => object
+                    .iter()
+                    .map(|(key, value)| {
+                        Vertex::JsonValue(Named {
+                            name: key.clone(),
+                            item: value.clone(),
+                        })
+                    })
+                    .collect(),
+                Value::Array(values) => values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        Vertex::JsonValue(Named {
+                            name: index.to_string(),
+                            item: value.clone(),
+                        })
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            Box::new(entries.into_iter())
+        })
+    }
+
+    pub(super) fn field<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        name: &str,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        let name = name.to_string();
+        resolve_neighbors_with(contexts, move |vertex| {
+            let field = json_value(vertex).item.get(&name).map(|value| {
+                Vertex::JsonValue(Named {
+                    name: name.clone(),
+                    item: value.clone(),
+                })
+            });
+            Box::new(field.into_iter())
+        })
+    }
+}
+
+pub(super) fn resolve_link_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "operation" => link::operation(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Link'")
+        }
+    }
+}
+
+mod link {
+    use openapiv3::{Link, LinkOperation};
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::{refs::Resolved, utils::Named};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn operation<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let link: &Named<Resolved<Link>> = vertex
+                .as_link()
+                .expect("conversion failed, vertex was not a Link");
+            let document = &link.item.document;
+            let operation = match &link.item.item.operation {
+                LinkOperation::OperationId(id) => document.operation_by_id(id),
+                LinkOperation::OperationRef(reference) => document.operation_by_ref(reference),
+            };
+            Box::new(operation.into_iter().map(Vertex::Operation))
+        })
+    }
+}
+
+pub(super) fn resolve_media_type_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "examples" => media_type::examples(contexts, resolve_info),
+        "schema" => media_type::schema(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'MediaType'")
+        }
+    }
+}
+
+mod media_type {
+    use openapiv3::MediaType;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::{
+        refs::Resolved,
+        utils::{self, media_type_schema, Named},
+    };
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn examples<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let media_type: &Named<Resolved<MediaType>> = vertex
+                .as_media_type()
+                .expect("conversion failed, vertex was not a MediaType");
+            let examples = utils::examples(
+                media_type.item.item.example.as_ref(),
+                &media_type.item.item.examples,
+                media_type.item.location.as_deref(),
+                &media_type.item.document,
+                media_type_schema(&media_type.item),
+            );
+            Box::new(examples.into_iter().map(Vertex::Example))
+        })
+    }
+
+    pub(super) fn schema<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let media_type: &Named<Resolved<MediaType>> = vertex
+                .as_media_type()
+                .expect("conversion failed, vertex was not a MediaType");
+            let schema = media_type_schema(&media_type.item);
+            Box::new(schema.into_iter().map(Vertex::Schema))
+        })
+    }
+}
+
+pub(super) fn resolve_security_scheme_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    type_name: &str,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "flows" => security_scheme::flows(contexts, resolve_info),
+        "xAmazonApigatewayAuthorizer" => {
+            security_scheme::x_amazon_apigateway_authorizer(contexts, resolve_info)
+        }
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type '{type_name}'")
+        }
+    }
+}
+
+mod security_scheme {
+    use openapiv3::SecurityScheme;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::{
+        aws::AmazonApigatewayAuthorizer,
+        refs::Resolved,
+        utils::{Named, OAuthFlow},
+    };
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn flows<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let scheme: &Named<Resolved<SecurityScheme>> = vertex
+                .as_o_auth2_security_scheme()
+                .expect("conversion failed, vertex was not a OAuth2SecurityScheme");
+            match &scheme.item.item {
+                SecurityScheme::OAuth2 { flows, .. } => Box::new(
+                    OAuthFlow::from_flows(flows)
+                        .into_iter()
+                        .map(Vertex::OAuthFlow),
+                ),
+                _ => Box::new(std::iter::empty()),
+            }
+        })
+    }
+
+    pub(super) fn x_amazon_apigateway_authorizer<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let scheme: &Named<Resolved<SecurityScheme>> = vertex
+                .as_any_security_scheme()
+                .expect("conversion failed, vertex was not a SecurityScheme");
+            let extensions = match &scheme.item.item {
+                SecurityScheme::APIKey { extensions, .. }
+                | SecurityScheme::HTTP { extensions, .. }
+                | SecurityScheme::OAuth2 { extensions, .. }
+                | SecurityScheme::OpenIDConnect { extensions, .. } => extensions,
+            };
+            Box::new(
+                AmazonApigatewayAuthorizer::from_extensions(extensions)
+                    .into_iter()
+                    .map(Vertex::AmazonApigatewayAuthorizer),
+            )
+        })
+    }
+}
+
+pub(super) fn resolve_oauth_flow_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "scopes" => oauth_flow::scopes(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'OAuthFlow'")
+        }
+    }
+}
+
+mod oauth_flow {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::utils::{Named, OAuthFlow};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn scopes<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let flow: &OAuthFlow = vertex
+                .as_o_auth_flow()
+                .expect("conversion failed, vertex was not a OAuthFlow");
+            let scopes: Vec<Vertex> = flow
+                .scopes
+                .iter()
+                .map(|(name, description)| {
+                    Vertex::OAuthScope(Named {
+                        name: name.clone(),
+                        item: description.clone(),
+                    })
+                })
+                .collect();
+            Box::new(scopes.into_iter())
+        })
+    }
+}
+
+pub(super) fn resolve_operation_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "effectiveParameters" => operation::effective_parameters(contexts, resolve_info),
+        "parameters" => operation::parameters(contexts, resolve_info),
+        "requestBody" => operation::request_body(contexts, resolve_info),
+        "callbacks" => operation::callbacks(contexts, resolve_info),
+        "externalDocs" => operation::external_docs(contexts, resolve_info),
+        "extensions" => extensions(contexts, resolve_info),
+        "responses" => operation::responses(contexts, resolve_info),
+        "security" => operation::security(contexts, resolve_info),
+        "servers" => operation::servers(contexts, resolve_info),
+        "xAmazonApigatewayRequestValidator" => {
+            operation::x_amazon_apigateway_request_validator(contexts, resolve_info)
+        }
+        "xAmazonApigatewayIntegration" => {
+            operation::x_amazon_apigateway_integration(contexts, resolve_info)
+        }
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Operation'")
+        }
+    }
+}
+
+mod operation {
+
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::{
+        refs::join,
+        utils::{Named, Operator, Route},
+    };
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn effective_parameters<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let operation: &Operator = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+            Box::new(
+                operation
+                    .effective_parameters()
+                    .into_iter()
+                    .map(Vertex::Parameter),
+            )
+        })
+    }
+
+    pub(super) fn parameters<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let operation: &Operator = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+            let location = operation.location.as_deref();
+            let parameters: Vec<Vertex> = operation
+                .operation
+                .parameters
+                .iter()
+                .enumerate()
+                .filter_map(|(i, parameter)| {
+                    let parameter = operation.document.resolve(parameter)?;
+                    Some(parameter.at(join(location, &["parameters", &i.to_string()])))
+                })
+                .map(Vertex::Parameter)
+                .collect();
+            Box::new(parameters.into_iter())
+        })
+    }
+
+    pub(super) fn request_body<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let operation: &Operator = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+            let request_body = operation.request_body();
+            Box::new(request_body.into_iter().map(Vertex::RequestBody))
+        })
+    }
+
+    pub(super) fn responses<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let operation: &Operator = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+            Box::new(operation.responses().into_iter().map(Vertex::Response))
+        })
+    }
+
+    pub(super) fn security<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let operation: &Operator = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+            super::security_requirements(operation.effective_security(), &operation.document)
+        })
+    }
+
+    pub(super) fn servers<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let operation: &Operator = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+            super::servers(operation.effective_servers())
+        })
+    }
+
+    pub(super) fn callbacks<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let operation: &Operator = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+            let document = &operation.document;
+            let callbacks: Vec<Vertex> = operation
+                .operation
+                .callbacks
+                .iter()
+                .flat_map(|(name, callback)| {
+                    callback.iter().map(move |(expression, item)| {
+                        Vertex::Callback(Named {
+                            name: name.clone(),
+                            item: Box::new(Route::from_path_item(
+                                expression, item, None, None, document,
+                            )),
+                        })
+                    })
+                })
+                .collect();
+            Box::new(callbacks.into_iter())
+        })
+    }
+
+    pub(super) fn external_docs<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let operation: &Operator = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+            Box::new(
+                operation
+                    .operation
+                    .external_docs
+                    .clone()
+                    .into_iter()
+                    .map(Vertex::ExternalDocs),
+            )
+        })
+    }
+
+    pub(super) fn x_amazon_apigateway_request_validator<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let operation: &Operator = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+            Box::new(
+                operation
+                    .request_validator()
+                    .into_iter()
+                    .map(Vertex::AmazonApigatewayRequestValidator),
+            )
+        })
+    }
+
+    pub(super) fn x_amazon_apigateway_integration<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let operation: &Operator = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+
+            match &operation.aws {
+                Some(value) => Box::new(std::iter::once(Vertex::AmazonApigatewayIntegration(
+                    value.as_ref().clone(),
+                ))),
+                None => Box::new(std::iter::empty()),
+            }
+        })
+    }
+}
+
+pub(super) fn resolve_parameter_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "examples" => parameter::examples(contexts, resolve_info),
+        "extensions" => extensions(contexts, resolve_info),
+        "schema" => parameter::schema(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Parameter'")
+        }
+    }
+}
+
+mod parameter {
+    use openapiv3::Parameter;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::{
+        refs::Resolved,
+        utils::{self, parameter_schema},
+    };
+
+    use super::super::vertex::Vertex;
+
+    fn parameter(vertex: &Vertex) -> &Resolved<Parameter> {
+        vertex
+            .as_parameter()
+            .expect("conversion failed, vertex was not a Parameter")
+    }
+
+    pub(super) fn examples<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let parameter = parameter(vertex);
+            let data = parameter.item.parameter_data_ref();
+            let examples = utils::examples(
+                data.example.as_ref(),
+                &data.examples,
+                parameter.location.as_deref(),
+                &parameter.document,
+                parameter_schema(parameter),
+            );
+            Box::new(examples.into_iter().map(Vertex::Example))
+        })
+    }
+
+    pub(super) fn schema<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let schema = parameter_schema(parameter(vertex));
+            Box::new(schema.into_iter().map(Vertex::Schema))
+        })
+    }
+}
+
+pub(super) fn resolve_path_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "delete" => path::delete(contexts, resolve_info),
+        "extensions" => extensions(contexts, resolve_info),
+        "get" => path::get(contexts, resolve_info),
+        "head" => path::head(contexts, resolve_info),
+        "operations" => path::operations(contexts, resolve_info),
+        "options" => path::options(contexts, resolve_info),
+        "parameters" => path::parameters(contexts, resolve_info),
+        "patch" => path::patch(contexts, resolve_info),
+        "post" => path::post(contexts, resolve_info),
+        "put" => path::put(contexts, resolve_info),
+        "servers" => path::servers(contexts, resolve_info),
+        "trace" => path::trace(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Path'")
+        }
+    }
+}
+
+mod path {
+
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::{refs::join, utils::Route};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn delete<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let route: &Route = vertex
+                .as_path()
+                .expect("conversion failed, vertex was not a Path");
+
+            match &route.delete {
+                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
+                None => Box::new(std::iter::empty()),
+            }
+        })
+    }
+
+    pub(super) fn get<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let route: &Route = vertex
+                .as_path()
+                .expect("conversion failed, vertex was not a Path");
+
+            match &route.get {
+                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
+                None => Box::new(std::iter::empty()),
+            }
+        })
+    }
+
+    pub(super) fn head<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let route: &Route = vertex
+                .as_path()
+                .expect("conversion failed, vertex was not a Path");
+
+            match &route.head {
+                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
+                None => Box::new(std::iter::empty()),
+            }
+        })
+    }
+
+    pub(super) fn operations<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let route: &Route = vertex
+                .as_path()
+                .expect("conversion failed, vertex was not a Path");
+
+            let operations: Vec<Vertex> = route
+                .operations()
+                .into_iter()
+                .cloned()
+                .map(Vertex::Operation)
+                .collect();
+            Box::new(operations.into_iter())
+        })
+    }
+
+    pub(super) fn options<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let route: &Route = vertex
+                .as_path()
+                .expect("conversion failed, vertex was not a Path");
+
+            match &route.options {
+                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
+                None => Box::new(std::iter::empty()),
+            }
+        })
+    }
+
+    pub(super) fn parameters<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let route: &Route = vertex
+                .as_path()
+                .expect("conversion failed, vertex was not a Path");
+            let location = route.item_location();
+            let parameters: Vec<Vertex> = route
+                .parameters
+                .iter()
+                .enumerate()
+                .filter_map(|(i, parameter)| {
+                    let parameter = route.document.resolve(parameter)?;
+                    Some(parameter.at(join(location.as_deref(), &["parameters", &i.to_string()])))
+                })
+                .map(Vertex::Parameter)
+                .collect();
+            Box::new(parameters.into_iter())
+        })
+    }
+
+    pub(super) fn patch<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let route: &Route = vertex
+                .as_path()
+                .expect("conversion failed, vertex was not a Path");
+
+            match &route.patch {
+                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
+                None => Box::new(std::iter::empty()),
+            }
+        })
+    }
+
+    pub(super) fn post<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let route: &Route = vertex
+                .as_path()
+                .expect("conversion failed, vertex was not a Path");
+
+            match &route.post {
+                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
+                None => Box::new(std::iter::empty()),
+            }
+        })
+    }
+
+    pub(super) fn servers<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let route: &Route = vertex
+                .as_path()
+                .expect("conversion failed, vertex was not a Path");
+            super::servers(route.effective_servers())
+        })
+    }
+
+    pub(super) fn trace<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let route: &Route = vertex
+                .as_path()
+                .expect("conversion failed, vertex was not a Path");
+
+            match &route.trace {
+                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
+                None => Box::new(std::iter::empty()),
+            }
+        })
+    }
+
+    pub(super) fn put<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let route: &Route = vertex
+                .as_path()
+                .expect("conversion failed, vertex was not a Path");
+
+            match &route.put {
+                Some(op) => Box::new(std::iter::once(Vertex::Operation(op.clone()))),
+                None => Box::new(std::iter::empty()),
+            }
+        })
+    }
+}
+
+pub(super) fn resolve_schema_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "additionalProperties" => schema::single(contexts, "additionalProperties", resolve_info),
+        "allOf" => schema::list(contexts, "allOf", resolve_info),
+        "anyOf" => schema::list(contexts, "anyOf", resolve_info),
+        "examples" => schema::examples(contexts, resolve_info),
+        "extensions" => extensions(contexts, resolve_info),
+        "items" => schema::single(contexts, "items", resolve_info),
+        "not" => schema::single(contexts, "not", resolve_info),
+        "oneOf" => schema::list(contexts, "oneOf", resolve_info),
+        "properties" => schema::properties(contexts, resolve_info),
+        "subschemas" => schema::subschemas(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Schema'")
+        }
+    }
+}
+
+mod schema {
+    use serde_json::Value;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use indexmap::IndexMap;
+
+    use crate::utils::{self, SchemaNode, SchemaProperty};
+
+    use super::super::vertex::Vertex;
+
+    fn schema(vertex: &Vertex) -> &SchemaNode {
+        vertex
+            .as_schema()
+            .expect("conversion failed, vertex was not a Schema")
+    }
+
+    /// Schemas under a keyword that holds a single schema, e.g. `items`
+    fn single_children(schema: &SchemaNode, keyword: &str) -> Vec<SchemaNode> {
+        // `additionalProperties` can also be a boolean
+        schema
+            .json
+            .get(keyword)
+            .filter(|value| value.is_object())
+            .and_then(|value| schema.child(value, &[keyword]))
+            .into_iter()
+            .collect()
+    }
+
+    /// Schemas under a keyword that holds a list of schemas, e.g. `allOf`
+    fn list_children(schema: &SchemaNode, keyword: &str) -> Vec<SchemaNode> {
+        schema
+            .json
+            .get(keyword)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .filter_map(|(i, value)| schema.child(value, &[keyword, &i.to_string()]))
+            .collect()
+    }
+
+    fn property_values(schema: &SchemaNode) -> impl Iterator<Item = (&String, &Value)> {
+        schema
+            .json
+            .get("properties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+    }
+
+    pub(super) fn single<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        keyword: &'static str,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let children = single_children(schema(vertex), keyword);
+            Box::new(children.into_iter().map(Vertex::Schema))
+        })
+    }
+
+    pub(super) fn list<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        keyword: &'static str,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let children = list_children(schema(vertex), keyword);
+            Box::new(children.into_iter().map(Vertex::Schema))
+        })
+    }
+
+    pub(super) fn examples<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let schema = schema(vertex);
+            // Schemas have a single `example`, since 3.1 `examples` become one when read
+            let examples = utils::examples(
+                schema.json.get("example"),
+                &IndexMap::new(),
+                schema.location.as_deref(),
+                &schema.schema.document,
+                Some(schema.clone()),
+            );
+            Box::new(examples.into_iter().map(Vertex::Example))
+        })
+    }
+
+    pub(super) fn properties<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let schema = schema(vertex);
+            let required: Vec<&str> = schema
+                .json
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            let properties: Vec<Vertex> = property_values(schema)
+                .map(|(name, value)| {
+                    Vertex::SchemaProperty(SchemaProperty {
+                        name: name.clone(),
+                        required: required.contains(&name.as_str()),
+                        schema: schema.child(value, &["properties", name]),
+                    })
+                })
+                .collect();
+            Box::new(properties.into_iter())
+        })
+    }
+
+    pub(super) fn subschemas<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let schema = schema(vertex);
+            let mut children: Vec<SchemaNode> = property_values(schema)
+                .filter_map(|(name, value)| schema.child(value, &["properties", name]))
+                .collect();
+            children.extend(single_children(schema, "items"));
+            for keyword in ["allOf", "oneOf", "anyOf"] {
+                children.extend(list_children(schema, keyword));
+            }
+            children.extend(single_children(schema, "not"));
+            children.extend(single_children(schema, "additionalProperties"));
+            Box::new(children.into_iter().map(Vertex::Schema))
+        })
+    }
+}
+
+pub(super) fn resolve_schema_property_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "schema" => schema_property::schema(contexts, resolve_info),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'SchemaProperty'"
+            )
+        }
+    }
+}
+
+mod schema_property {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::utils::SchemaProperty;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn schema<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let property: &SchemaProperty = vertex
+                .as_schema_property()
+                .expect("conversion failed, vertex was not a SchemaProperty");
+            Box::new(property.schema.clone().into_iter().map(Vertex::Schema))
+        })
+    }
+}
+
+pub(super) fn resolve_security_requirement_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "schemes" => security_requirement::schemes(contexts, resolve_info),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'SecurityRequirement'"
+            )
+        }
+    }
+}
+
+mod security_requirement {
+    use openapiv3::SecurityRequirement;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::{refs::Resolved, utils::SecuritySchemeRequirement};
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn schemes<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let requirement: &Resolved<SecurityRequirement> = vertex
+                .as_security_requirement()
+                .expect("conversion failed, vertex was not a SecurityRequirement");
+            let schemes: Vec<Vertex> = requirement
+                .item
+                .iter()
+                .map(|(name, scopes)| {
+                    Vertex::SecuritySchemeRequirement(SecuritySchemeRequirement {
+                        name: name.clone(),
+                        scopes: scopes.clone(),
+                        document: requirement.document.clone(),
+                    })
+                })
+                .collect();
+            Box::new(schemes.into_iter())
+        })
+    }
+}
+
+pub(super) fn resolve_security_scheme_requirement_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "scheme" => security_scheme_requirement::scheme(contexts, resolve_info),
+        _ => {
+            unreachable!(
+                "attempted to resolve unexpected edge '{edge_name}' on type 'SecuritySchemeRequirement'"
+            )
+        }
+    }
+}
+
+mod security_scheme_requirement {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::utils::SecuritySchemeRequirement;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn scheme<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let requirement: &SecuritySchemeRequirement = vertex
+                .as_security_scheme_requirement()
+                .expect("conversion failed, vertex was not a SecuritySchemeRequirement");
+            let scheme = requirement
+                .document
+                .security_schemes()
+                .into_iter()
+                .find(|scheme| scheme.name == requirement.name);
+            Box::new(scheme.into_iter().map(Vertex::security_scheme))
+        })
+    }
+}
+
+pub(super) fn resolve_server_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "variables" => server::variables(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Server'")
+        }
+    }
+}
+
+mod server {
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::utils::Named;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn variables<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let server = vertex
+                .as_server()
+                .expect("conversion failed, vertex was not a Server");
+            let variables: Vec<Vertex> = server
+                .variables
+                .iter()
+                .flatten()
+                .map(|(name, variable)| {
+                    Vertex::ServerVariable(Named {
+                        name: name.clone(),
+                        item: variable.clone(),
+                    })
+                })
+                .collect();
+            Box::new(variables.into_iter())
+        })
+    }
+}
+
+pub(super) fn resolve_request_body_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "content" => request_body::content(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'RequestBody'")
+        }
+    }
+}
+
+mod request_body {
+    use openapiv3::RequestBody;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::refs::Resolved;
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn content<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let request_body: &Resolved<RequestBody> = vertex
+                .as_request_body()
+                .expect("conversion failed, vertex was not a RequestBody");
+            super::content(
+                &request_body.item.content,
+                request_body.location.as_deref(),
+                &request_body.document,
+            )
+        })
+    }
+}
+
+pub(super) fn resolve_response_edge<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    edge_name: &str,
+    _parameters: &EdgeParameters,
+    resolve_info: &ResolveEdgeInfo,
+) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+    match edge_name {
+        "content" => response::content(contexts, resolve_info),
+        "extensions" => extensions(contexts, resolve_info),
+        "headers" => response::headers(contexts, resolve_info),
+        "links" => response::links(contexts, resolve_info),
+        _ => {
+            unreachable!("attempted to resolve unexpected edge '{edge_name}' on type 'Response'")
+        }
+    }
+}
+
+mod response {
+    use openapiv3::Response;
+    use trustfall::provider::{
+        resolve_neighbors_with, AsVertex, ContextIterator, ContextOutcomeIterator, ResolveEdgeInfo,
+        VertexIterator,
+    };
+
+    use crate::{
+        refs::{join, Resolved},
+        utils::Named,
+    };
+
+    use super::super::vertex::Vertex;
+
+    pub(super) fn content<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let response: &Named<Resolved<Response>> = vertex
+                .as_response()
+                .expect("conversion failed, vertex was not a Response");
+            super::content(
+                &response.item.item.content,
+                response.item.location.as_deref(),
+                &response.item.document,
+            )
+        })
+    }
+
+    pub(super) fn headers<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let response: &Named<Resolved<Response>> = vertex
+                .as_response()
+                .expect("conversion failed, vertex was not a Response");
+            let document = &response.item.document;
+            let headers: Vec<Vertex> = response
+                .item
+                .item
+                .headers
+                .iter()
+                .filter_map(|(name, header)| {
+                    let location = join(response.item.location.as_deref(), &["headers", name]);
+                    Some(Vertex::Header(Named {
+                        name: name.clone(),
+                        item: document.resolve(header)?.at(location),
+                    }))
+                })
+                .collect();
+            Box::new(headers.into_iter())
+        })
+    }
+
+    pub(super) fn links<'a, V: AsVertex<Vertex> + 'a>(
+        contexts: ContextIterator<'a, V>,
+        _resolve_info: &ResolveEdgeInfo,
+    ) -> ContextOutcomeIterator<'a, V, VertexIterator<'a, Vertex>> {
+        resolve_neighbors_with(contexts, move |vertex| {
+            let response: &Named<Resolved<Response>> = vertex
+                .as_response()
+                .expect("conversion failed, vertex was not a Response");
+            let document = &response.item.document;
+            let links: Vec<Vertex> = response
+                .item
+                .item
+                .links
+                .iter()
+                .filter_map(|(name, link)| {
+                    let location = join(response.item.location.as_deref(), &["links", name]);
+                    Some(Vertex::Link(Named {
+                        name: name.clone(),
+                        item: document.resolve(link)?.at(location),
+                    }))
+                })
+                .collect();
+            Box::new(links.into_iter())
         })
     }
 }
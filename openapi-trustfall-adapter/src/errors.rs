@@ -16,4 +16,12 @@ pub enum OpenAPIAdapterErrors {
     FailedToOpenFile(#[from] std::io::Error),
     #[error("Failed to serialize to an OpenAPI struct")]
     FailedToSerializeToOpenAPI(#[from] serde_yaml::Error),
+    #[error("Failed to deserialize to an OpenAPI struct: {0}")]
+    FailedToDeserializeToOpenAPI(#[from] serde_json::Error),
+    #[error("Document doesn't declare an OpenAPI version")]
+    MissingVersion,
+    #[error("Unsupported OpenAPI version {0}")]
+    UnsupportedVersion(String),
+    #[error("Invalid lint rule {0}: {1}")]
+    InvalidLintRule(String, String),
 }
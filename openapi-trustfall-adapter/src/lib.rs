@@ -1,11 +1,25 @@
 mod adapter_impl;
+mod aws;
+mod diff;
+mod document;
 mod edges;
 pub mod errors;
+mod lint;
+mod openapi31;
 mod properties;
+mod provenance;
+mod refs;
+mod spans;
+mod swagger2;
+mod template;
 mod utils;
+mod validation;
 mod vertex;
 
 pub use adapter_impl::OpenApiAdapter;
+pub use diff::OpenApiDiffAdapter;
+pub use lint::{LintRule, LintSeverity, LintViolation};
+pub use spans::Position;
 
 #[cfg(test)]
 mod tests;
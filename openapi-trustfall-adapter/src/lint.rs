@@ -0,0 +1,190 @@
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr, sync::Arc};
+
+use serde::Deserialize;
+use trustfall::{execute_query, FieldValue, TransparentValue};
+
+use crate::{
+    adapter_impl::OpenApiAdapter,
+    errors::OpenAPIAdapterErrors,
+    spans::Position,
+    utils::{find_files, open_file},
+};
+
+/// The lint rules that come with the crate, in the order they're run
+const BUNDLED_RULES: [&str; 6] = [
+    include_str!("rules/operation-operationId.yaml"),
+    include_str!("rules/operation-operationId-unique.yaml"),
+    include_str!("rules/operation-tag-defined.yaml"),
+    include_str!("rules/operation-4xx-response.yaml"),
+    include_str!("rules/paths-kebab-case.yaml"),
+    include_str!("rules/no-unused-components.yaml"),
+];
+
+/// How serious a lint rule's violations are
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// A lint rule, which is a Trustfall query whose results are the rule's violations.
+///
+/// Rules are read from YAML, e.g.
+///
+/// ```yaml
+/// id: operation-summary
+/// description: Operations have a summary
+/// severity: warning
+/// message: "{method} {path} has no summary"
+/// query: |
+///   query {
+///       Paths {
+///           path @output
+///           operations {
+///               method @output
+///               summary @filter(op: "is_null")
+///               sourceFile @output
+///               line @output
+///               column @output
+///           }
+///       }
+///   }
+/// ```
+///
+/// Each `{name}` in the message is replaced by the query's `name` output. The `sourceFile`,
+/// `line` and `column` outputs say where the violation is, and `arguments` gives the values
+/// of the query's `$` variables.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LintRule {
+    pub id: String,
+    pub description: String,
+    pub severity: LintSeverity,
+    pub message: String,
+    pub query: String,
+    #[serde(default)]
+    pub arguments: BTreeMap<String, TransparentValue>,
+}
+
+impl LintRule {
+    /// The rules that come with the crate: `operation-operationId`,
+    /// `operation-operationId-unique`, `operation-tag-defined`, `operation-4xx-response`,
+    /// `paths-kebab-case` and `no-unused-components`
+    pub fn bundled() -> Vec<Self> {
+        BUNDLED_RULES
+            .iter()
+            .map(|rule| rule.parse().expect("bundled lint rules are valid"))
+            .collect()
+    }
+
+    /// Reads a rule from a YAML file
+    pub fn from_file(file: PathBuf) -> Result<Self, OpenAPIAdapterErrors> {
+        let contents = open_file(file.clone())?;
+        contents.parse().map_err(|e| match e {
+            OpenAPIAdapterErrors::InvalidLintRule(_, message) => {
+                OpenAPIAdapterErrors::InvalidLintRule(file.display().to_string(), message)
+            }
+            e => e,
+        })
+    }
+
+    /// Reads every `.yaml` and `.yml` file in a directory as a rule, in order of their paths
+    pub fn from_path(path: PathBuf) -> Result<Vec<Self>, OpenAPIAdapterErrors> {
+        if !path.is_dir() {
+            return Err(OpenAPIAdapterErrors::PathIsNotADirectory(path));
+        }
+        let mut files = find_files(&path, "yaml".as_ref());
+        files.extend(find_files(&path, "yml".as_ref()));
+        files.sort();
+        files.into_iter().map(Self::from_file).collect()
+    }
+}
+
+impl FromStr for LintRule {
+    type Err = OpenAPIAdapterErrors;
+
+    /// Reads a rule from YAML
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        serde_yaml::from_str(contents).map_err(|e| {
+            OpenAPIAdapterErrors::InvalidLintRule("<string>".to_string(), e.to_string())
+        })
+    }
+}
+
+/// A result of a lint rule's query
+#[derive(Debug, Clone)]
+pub struct LintViolation {
+    /// The rule's id
+    pub rule: String,
+    pub severity: LintSeverity,
+    pub message: String,
+    /// File the violation is in, relative to the spec directory
+    pub source_file: Option<String>,
+    /// Where the violation is in its file, when the query gives its `line` and `column`
+    pub position: Option<Position>,
+    /// Everything the query output for the violation
+    pub outputs: BTreeMap<Arc<str>, FieldValue>,
+}
+
+impl LintViolation {
+    fn new(rule: &LintRule, outputs: BTreeMap<Arc<str>, FieldValue>) -> Self {
+        let mut message = rule.message.clone();
+        for (name, value) in &outputs {
+            message = message.replace(&format!("{{{name}}}"), &display(value));
+        }
+        let number = |name: &str| match outputs.get(name) {
+            Some(FieldValue::Int64(value)) => usize::try_from(*value).ok(),
+            Some(FieldValue::Uint64(value)) => usize::try_from(*value).ok(),
+            _ => None,
+        };
+        let position = number("line")
+            .zip(number("column"))
+            .map(|(line, column)| Position { line, column });
+        let source_file = match outputs.get("sourceFile") {
+            Some(FieldValue::String(file)) => Some(file.to_string()),
+            _ => None,
+        };
+        Self {
+            rule: rule.id.clone(),
+            severity: rule.severity,
+            message,
+            source_file,
+            position,
+            outputs,
+        }
+    }
+}
+
+/// Runs the rules against the adapter's document, giving the violations of each rule in turn
+pub(crate) fn lint(
+    adapter: Arc<OpenApiAdapter>,
+    rules: &[LintRule],
+) -> Result<Vec<LintViolation>, OpenAPIAdapterErrors> {
+    let mut violations = Vec::new();
+    for rule in rules {
+        let results = execute_query(
+            OpenApiAdapter::schema(),
+            adapter.clone(),
+            &rule.query,
+            rule.arguments.clone(),
+        )
+        .map_err(|e| OpenAPIAdapterErrors::InvalidLintRule(rule.id.clone(), e.to_string()))?;
+        violations.extend(results.map(|outputs| LintViolation::new(rule, outputs)));
+    }
+    Ok(violations)
+}
+
+/// A query output as it's written into a message. Lists are joined with commas
+fn display(value: &FieldValue) -> String {
+    match value {
+        FieldValue::Null => String::new(),
+        FieldValue::String(value) => value.to_string(),
+        FieldValue::Int64(value) => value.to_string(),
+        FieldValue::Uint64(value) => value.to_string(),
+        FieldValue::Float64(value) => value.to_string(),
+        FieldValue::Boolean(value) => value.to_string(),
+        FieldValue::List(values) => values.iter().map(display).collect::<Vec<_>>().join(", "),
+        value => format!("{value:?}"),
+    }
+}
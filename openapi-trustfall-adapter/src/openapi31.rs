@@ -0,0 +1,308 @@
+use serde_json::{Map, Value};
+
+use crate::{refs::escape, utils::Diagnostic};
+
+/// Keys whose values are literal data rather than OpenAPI objects, so they are never rewritten
+const DATA_KEYS: [&str; 5] = ["example", "examples", "default", "enum", "const"];
+
+/// Keys in a schema whose value is a map of names to schemas
+const SCHEMA_MAP_KEYS: [&str; 4] = [
+    "properties",
+    "patternProperties",
+    "$defs",
+    "dependentSchemas",
+];
+
+/// Keys in a schema whose value is a single schema
+const SCHEMA_KEYS: [&str; 9] = [
+    "items",
+    "additionalProperties",
+    "not",
+    "contains",
+    "if",
+    "then",
+    "else",
+    "propertyNames",
+    "unevaluatedProperties",
+];
+
+/// Keys in a schema whose value is a list of schemas
+const SCHEMA_LIST_KEYS: [&str; 4] = ["allOf", "oneOf", "anyOf", "prefixItems"];
+
+/// Schema keywords that the 3.0 model has no place for, so they're lost when a document is read
+const DROPPED_KEYS: [&str; 12] = [
+    "$defs",
+    "prefixItems",
+    "if",
+    "then",
+    "else",
+    "patternProperties",
+    "dependentSchemas",
+    "dependentRequired",
+    "contains",
+    "propertyNames",
+    "unevaluatedProperties",
+    "unevaluatedItems",
+];
+
+/// Rewrites an OpenAPI 3.1 document in place so that it can be read with the 3.0 model.
+///
+/// JSON Schema 2020-12 constructs are mapped to their 3.0 equivalents where one exists:
+/// `type` arrays become `type` plus `nullable` (or `anyOf` for several types), `null`
+/// variants in `oneOf`/`anyOf` become `nullable`, `const` becomes a single value `enum`,
+/// numeric `exclusiveMinimum`/`exclusiveMaximum` become their boolean forms and
+/// schema `examples` become `example`.
+pub(crate) fn normalize(document: &mut Value) {
+    if let Some(object) = document.as_object_mut() {
+        object
+            .entry("paths")
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+    if let Some(schemas) = document
+        .pointer_mut("/components/schemas")
+        .and_then(Value::as_object_mut)
+    {
+        schemas.values_mut().for_each(normalize_schema);
+    }
+    normalize_objects(document);
+}
+
+/// Walks the document looking for `schema` keys, which is where schemas hang off
+/// parameters, headers and media types
+fn normalize_objects(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                if key == "schema" {
+                    normalize_schema(value);
+                } else if !DATA_KEYS.contains(&key.as_str()) {
+                    normalize_objects(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(normalize_objects),
+        _ => {}
+    }
+}
+
+pub(crate) fn normalize_schema(schema: &mut Value) {
+    // Boolean schemas aren't supported by the 3.0 model
+    if let Value::Bool(allowed) = schema {
+        *schema = if *allowed {
+            Value::Object(Map::new())
+        } else {
+            serde_json::json!({ "not": {} })
+        };
+        return;
+    }
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+
+    // Before the variants themselves are normalized, while `type: "null"` is still recognisable
+    normalize_null_variants(object);
+    for key in SCHEMA_MAP_KEYS {
+        if let Some(schemas) = object.get_mut(key).and_then(Value::as_object_mut) {
+            schemas.values_mut().for_each(normalize_schema);
+        }
+    }
+    for key in SCHEMA_KEYS {
+        if let Some(schema) = object.get_mut(key) {
+            // `additionalProperties: false` is valid in 3.0 as is
+            if !(key == "additionalProperties" && schema.is_boolean()) {
+                normalize_schema(schema);
+            }
+        }
+    }
+    for key in SCHEMA_LIST_KEYS {
+        if let Some(schemas) = object.get_mut(key).and_then(Value::as_array_mut) {
+            schemas.iter_mut().for_each(normalize_schema);
+        }
+    }
+
+    normalize_type(object);
+
+    if let Some(value) = object.remove("const") {
+        object.insert("enum".to_string(), Value::Array(vec![value]));
+    }
+    if let Some(Value::Array(examples)) = object.remove("examples") {
+        if let Some(example) = examples.into_iter().next() {
+            object.entry("example").or_insert(example);
+        }
+    }
+    for (exclusive, bound) in [
+        ("exclusiveMinimum", "minimum"),
+        ("exclusiveMaximum", "maximum"),
+    ] {
+        if let Some(limit) = object.get(exclusive).filter(|limit| limit.is_number()) {
+            let limit = limit.clone();
+            object.insert(bound.to_string(), limit);
+            object.insert(exclusive.to_string(), Value::Bool(true));
+        }
+    }
+    if object.get("contentEncoding").and_then(Value::as_str) == Some("base64") {
+        object
+            .entry("format")
+            .or_insert_with(|| Value::String("byte".to_string()));
+    } else if object.contains_key("contentMediaType") {
+        object
+            .entry("format")
+            .or_insert_with(|| Value::String("binary".to_string()));
+    }
+}
+
+/// `type: [string, "null"]` becomes `type: string, nullable: true`
+fn normalize_type(object: &mut Map<String, Value>) {
+    let mut types = match object.get("type") {
+        Some(Value::Array(types)) => types.clone(),
+        Some(Value::String(t)) if t == "null" => vec![Value::String(t.clone())],
+        _ => return,
+    };
+    let count = types.len();
+    types.retain(|t| t.as_str() != Some("null"));
+    if types.len() != count {
+        object.insert("nullable".to_string(), Value::Bool(true));
+    }
+    match types.len() {
+        0 => {
+            object.remove("type");
+        }
+        1 => {
+            object.insert("type".to_string(), types.remove(0));
+        }
+        _ => {
+            object.remove("type");
+            let variants = Value::Array(
+                types
+                    .into_iter()
+                    .map(|t| serde_json::json!({ "type": t }))
+                    .collect(),
+            );
+            if object.contains_key("anyOf") {
+                let all_of = object
+                    .entry("allOf")
+                    .or_insert_with(|| Value::Array(Vec::new()));
+                if let Value::Array(all_of) = all_of {
+                    all_of.push(serde_json::json!({ "anyOf": variants }));
+                }
+            } else {
+                object.insert("anyOf".to_string(), variants);
+            }
+        }
+    }
+}
+
+/// `oneOf: [{$ref: ...}, {type: "null"}]` becomes `oneOf: [{$ref: ...}]` with `nullable: true`
+fn normalize_null_variants(object: &mut Map<String, Value>) {
+    let mut nullable = false;
+    for key in ["oneOf", "anyOf"] {
+        if let Some(Value::Array(variants)) = object.get_mut(key) {
+            let before = variants.len();
+            variants.retain(|variant| {
+                !(variant.get("type").and_then(Value::as_str) == Some("null")
+                    && variant.as_object().is_some_and(|v| v.len() == 1))
+            });
+            nullable |= variants.len() != before;
+        }
+    }
+    if nullable {
+        object.insert("nullable".to_string(), Value::Bool(true));
+    }
+}
+
+/// The schema keywords of a 3.1 document that are lost when it's read into the 3.0 model,
+/// including every schema `examples` entry after the first. Found before the document is
+/// normalized, at the same places [`normalize`] looks for schemas.
+pub(crate) fn dropped_keywords(document: &Value) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    if let Some(schemas) = document
+        .pointer("/components/schemas")
+        .and_then(Value::as_object)
+    {
+        for (name, schema) in schemas {
+            let location = format!("#/components/schemas/{}", escape(name));
+            schema_dropped_keywords(schema, &location, &mut diagnostics);
+        }
+    }
+    objects_dropped_keywords(document, "#", &mut diagnostics);
+    // A schema can be reached both ways, e.g. a property that's called `schema`
+    let mut found: Vec<Diagnostic> = Vec::new();
+    for diagnostic in diagnostics {
+        if !found.iter().any(|found| found.name == diagnostic.name) {
+            found.push(diagnostic);
+        }
+    }
+    found
+}
+
+fn objects_dropped_keywords(value: &Value, location: &str, diagnostics: &mut Vec<Diagnostic>) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                let location = format!("{location}/{}", escape(key));
+                if key == "schema" {
+                    schema_dropped_keywords(value, &location, diagnostics);
+                } else if !DATA_KEYS.contains(&key.as_str()) {
+                    objects_dropped_keywords(value, &location, diagnostics);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for (i, value) in values.iter().enumerate() {
+                objects_dropped_keywords(value, &format!("{location}/{i}"), diagnostics);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The keywords of a 3.1 schema at the location, and the schemas within it that are kept, that
+/// are lost when it's read into the 3.0 model
+pub(crate) fn schema_dropped_keywords(
+    schema: &Value,
+    location: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let Some(object) = schema.as_object() else {
+        return;
+    };
+    let mut dropped = |key: &str, message: String| {
+        diagnostics.push(Diagnostic {
+            kind: "dropped-keyword".to_string(),
+            name: format!("{location}/{}", escape(key)),
+            message,
+        })
+    };
+    for key in DROPPED_KEYS
+        .into_iter()
+        .filter(|key| object.contains_key(*key))
+    {
+        dropped(
+            key,
+            format!("{key} isn't supported by the OpenAPI 3.0 model"),
+        );
+    }
+    if let Some(Value::Array(examples)) = object.get("examples") {
+        if examples.len() > 1 {
+            let message = format!("Only the first of {} examples is kept", examples.len());
+            dropped("examples", message);
+        }
+    }
+    if let Some(properties) = object.get("properties").and_then(Value::as_object) {
+        for (name, property) in properties {
+            let location = format!("{location}/properties/{}", escape(name));
+            schema_dropped_keywords(property, &location, diagnostics);
+        }
+    }
+    for key in ["items", "additionalProperties", "not"] {
+        if let Some(schema) = object.get(key) {
+            schema_dropped_keywords(schema, &format!("{location}/{key}"), diagnostics);
+        }
+    }
+    for key in ["allOf", "oneOf", "anyOf"] {
+        let schemas = object.get(key).and_then(Value::as_array);
+        for (i, schema) in schemas.into_iter().flatten().enumerate() {
+            schema_dropped_keywords(schema, &format!("{location}/{key}/{i}"), diagnostics);
+        }
+    }
+}
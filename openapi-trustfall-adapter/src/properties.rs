@@ -6,8 +6,140 @@ use trustfall::{
     FieldValue,
 };
 
+use openapiv3::{
+    APIKeyLocation, Example, Header, Link, LinkOperation, Parameter, RequestBody, Response,
+    SecurityScheme, ServerVariable, Tag,
+};
+use serde_json::Value;
+
+use crate::{
+    aws::{extension, IntegrationResponse, RequestValidator},
+    document::Document,
+    provenance::MergeConflict,
+    refs::{ref_name, Resolved},
+    utils::{expand_server_url, parameter_location, Component, Named, SchemaNode},
+    validation::validate_example,
+};
+
 use super::vertex::Vertex;
 
+pub(super) fn resolve_amazon_apigateway_authorizer_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "authorizerCredentials" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_authorizer, authorizer_credentials),
+        ),
+        "authorizerUri" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_authorizer, authorizer_uri),
+        ),
+        "enableSimpleResponses" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_authorizer, enable_simple_responses),
+        ),
+        "identitySource" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_authorizer, identity_source),
+        ),
+        "identityValidationExpression" => resolve_property_with(
+            contexts,
+            field_property!(
+                as_amazon_apigateway_authorizer,
+                identity_validation_expression
+            ),
+        ),
+        "payloadFormatVersion" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_authorizer, payload_format_version),
+        ),
+        "providerArns" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_authorizer, provider_arns),
+        ),
+        "resultTtlInSeconds" => resolve_property_with(contexts, |vertex| {
+            let authorizer = vertex
+                .as_amazon_apigateway_authorizer()
+                .expect("conversion failed, vertex was not a AmazonApigatewayAuthorizer");
+            authorizer.effective_result_ttl().into()
+        }),
+        "type" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_authorizer, r_type),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'AmazonApigatewayAuthorizer'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_amazon_apigateway_cors_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "allowCredentials" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_cors, allow_credentials),
+        ),
+        "allowHeaders" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_cors, allow_headers),
+        ),
+        "allowMethods" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_cors, allow_methods),
+        ),
+        "allowOrigins" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_cors, allow_origins),
+        ),
+        "exposeHeaders" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_cors, expose_headers),
+        ),
+        "maxAge" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_cors, max_age),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'AmazonApigatewayCors'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_amazon_apigateway_gateway_response_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "responseType" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_gateway_response, name),
+        ),
+        "statusCode" => resolve_property_with(contexts, |vertex| {
+            let response = vertex
+                .as_amazon_apigateway_gateway_response()
+                .expect("conversion failed, vertex was not a AmazonApigatewayGatewayResponse");
+            response.item.status_code.clone().into()
+        }),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'AmazonApigatewayGatewayResponse'"
+            )
+        }
+    }
+}
+
 pub(super) fn resolve_amazon_apigateway_integration_property<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     property_name: &str,
@@ -18,10 +150,38 @@ pub(super) fn resolve_amazon_apigateway_integration_property<'a, V: AsVertex<Ver
             contexts,
             field_property!(as_amazon_apigateway_integration, arn),
         ),
+        "cacheKeyParameters" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration, cache_key_parameters),
+        ),
+        "cacheNamespace" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration, cache_namespace),
+        ),
+        "connectionId" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration, connection_id),
+        ),
+        "connectionType" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration, connection_type),
+        ),
+        "contentHandling" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration, content_handling),
+        ),
+        "credentials" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration, credentials),
+        ),
         "httpMethod" => resolve_property_with(
             contexts,
             field_property!(as_amazon_apigateway_integration, http_method),
         ),
+        "integrationSubtype" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration, integration_subtype),
+        ),
         "passthroughBehavior" => resolve_property_with(
             contexts,
             field_property!(as_amazon_apigateway_integration, pass_through_behavior),
@@ -54,64 +214,1229 @@ pub(super) fn resolve_amazon_apigateway_integration_property<'a, V: AsVertex<Ver
     }
 }
 
-pub(super) fn resolve_info_property<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_amazon_apigateway_integration_arn_property<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     property_name: &str,
     _resolve_info: &ResolveInfo,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
-        "description" => resolve_property_with(contexts, field_property!(as_info, description)),
-        "title" => resolve_property_with(contexts, field_property!(as_info, title)),
-        "version" => resolve_property_with(contexts, field_property!(as_info, version)),
+        "action" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration_arn, action),
+        ),
+        "actionType" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration_arn, action_type),
+        ),
+        "functionArn" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration_arn, function_arn),
+        ),
+        "functionName" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration_arn, function_name),
+        ),
+        "region" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration_arn, region),
+        ),
+        "service" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration_arn, service),
+        ),
+        "stateMachineArn" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_integration_arn, state_machine_arn),
+        ),
         _ => {
-            unreachable!("attempted to read unexpected property '{property_name}' on type 'Info'")
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'AmazonApigatewayIntegrationArn'"
+            )
         }
     }
 }
 
-pub(super) fn resolve_operation_property<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_amazon_apigateway_integration_response_property<
+    'a,
+    V: AsVertex<Vertex> + 'a,
+>(
     contexts: ContextIterator<'a, V>,
     property_name: &str,
     _resolve_info: &ResolveInfo,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn response(vertex: &Vertex) -> &Named<IntegrationResponse> {
+        vertex
+            .as_amazon_apigateway_integration_response()
+            .expect("conversion failed, vertex was not a AmazonApigatewayIntegrationResponse")
+    }
     match property_name {
-        "description" => {
-            resolve_property_with(contexts, field_property!(as_operation, description))
+        "contentHandling" => resolve_property_with(contexts, |vertex| {
+            response(vertex).item.content_handling.clone().into()
+        }),
+        "selectionPattern" => {
+            resolve_property_with(contexts, |vertex| response(vertex).name.clone().into())
         }
-        "summary" => resolve_property_with(contexts, field_property!(as_operation, summary)),
-        "method" => resolve_property_with(contexts, field_property!(as_operation, method)),
-        "tags" => resolve_property_with(contexts, field_property!(as_operation, tags)),
+        "statusCode" => resolve_property_with(contexts, |vertex| {
+            response(vertex).item.status_code.clone().into()
+        }),
         _ => {
             unreachable!(
-                "attempted to read unexpected property '{property_name}' on type 'Operation'"
+                "attempted to read unexpected property '{property_name}' on type 'AmazonApigatewayIntegrationResponse'"
             )
         }
     }
 }
 
-pub(super) fn resolve_path_property<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_amazon_apigateway_mapping_property<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     property_name: &str,
     _resolve_info: &ResolveInfo,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
-        "path" => resolve_property_with(contexts, field_property!(as_path, path)),
+        "key" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_mapping, name),
+        ),
+        "value" => resolve_property_with(
+            contexts,
+            field_property!(as_amazon_apigateway_mapping, item),
+        ),
         _ => {
-            unreachable!("attempted to read unexpected property '{property_name}' on type 'Path'")
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'AmazonApigatewayMapping'"
+            )
         }
     }
 }
 
-pub(super) fn resolve_tag_property<'a, V: AsVertex<Vertex> + 'a>(
+pub(super) fn resolve_diagnostic_property<'a, V: AsVertex<Vertex> + 'a>(
     contexts: ContextIterator<'a, V>,
     property_name: &str,
     _resolve_info: &ResolveInfo,
 ) -> ContextOutcomeIterator<'a, V, FieldValue> {
     match property_name {
-        "description" => resolve_property_with(contexts, field_property!(as_tag, description)),
-        "name" => resolve_property_with(contexts, field_property!(as_tag, name)),
+        "kind" => resolve_property_with(contexts, field_property!(as_diagnostic, kind)),
+        "message" => resolve_property_with(contexts, field_property!(as_diagnostic, message)),
+        "name" => resolve_property_with(contexts, field_property!(as_diagnostic, name)),
         _ => {
-            unreachable!("attempted to read unexpected property '{property_name}' on type 'Tag'")
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Diagnostic'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_document_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "openapiVersion" => resolve_property_with(contexts, field_property!(as_document, version)),
+        "sourceFile" => resolve_property_with(contexts, field_property!(as_document, source_file)),
+        "xAmazonApigatewayApiKeySource" => resolve_property_with(contexts, |vertex| {
+            let document = vertex
+                .as_document()
+                .expect("conversion failed, vertex was not a Document");
+            extension::<String>(
+                &document.openapi.extensions,
+                "x-amazon-apigateway-api-key-source",
+            )
+            .into()
+        }),
+        "xAmazonApigatewayBinaryMediaTypes" => resolve_property_with(contexts, |vertex| {
+            let document = vertex
+                .as_document()
+                .expect("conversion failed, vertex was not a Document");
+            extension::<Vec<String>>(
+                &document.openapi.extensions,
+                "x-amazon-apigateway-binary-media-types",
+            )
+            .unwrap_or_default()
+            .into()
+        }),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Document'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_example_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn example(vertex: &Vertex) -> &Named<Resolved<Example>> {
+        &vertex
+            .as_example()
+            .expect("conversion failed, vertex was not a Example")
+            .example
+    }
+    match property_name {
+        "description" => resolve_property_with(contexts, |vertex| {
+            example(vertex).item.item.description.clone().into()
+        }),
+        "externalValue" => resolve_property_with(contexts, |vertex| {
+            example(vertex).item.item.external_value.clone().into()
+        }),
+        "isValid" => resolve_property_with(contexts, |vertex| {
+            let example = vertex
+                .as_example()
+                .expect("conversion failed, vertex was not a Example");
+            validate_example(example).is_empty().into()
+        }),
+        "name" => resolve_property_with(contexts, |vertex| example(vertex).name.clone().into()),
+        "refName" | "refPath" => {
+            let is_name = property_name == "refName";
+            resolve_property_with(contexts, move |vertex| {
+                reference_property(&example(vertex).item.reference, is_name)
+            })
+        }
+        "summary" => resolve_property_with(contexts, |vertex| {
+            example(vertex).item.item.summary.clone().into()
+        }),
+        "valueJson" => resolve_property_with(contexts, |vertex| {
+            example(vertex)
+                .item
+                .item
+                .value
+                .as_ref()
+                .map(|value| value.to_string())
+                .into()
+        }),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Example'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_amazon_apigateway_request_validator_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn validator(vertex: &Vertex) -> &Named<RequestValidator> {
+        vertex
+            .as_amazon_apigateway_request_validator()
+            .expect("conversion failed, vertex was not a AmazonApigatewayRequestValidator")
+    }
+    match property_name {
+        "name" => resolve_property_with(contexts, |vertex| validator(vertex).name.clone().into()),
+        "validateRequestBody" => resolve_property_with(contexts, |vertex| {
+            validator(vertex).item.validate_request_body.into()
+        }),
+        "validateRequestParameters" => resolve_property_with(contexts, |vertex| {
+            validator(vertex).item.validate_request_parameters.into()
+        }),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'AmazonApigatewayRequestValidator'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_callback_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "expression" => resolve_property_with(contexts, |vertex| {
+            let callback = vertex
+                .as_callback()
+                .expect("conversion failed, vertex was not a Callback");
+            callback.item.path.clone().into()
+        }),
+        "name" => resolve_property_with(contexts, field_property!(as_callback, name)),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Callback'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_change_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "kind" => resolve_property_with(contexts, field_property!(as_change, kind)),
+        "location" => resolve_property_with(contexts, field_property!(as_change, location)),
+        "message" => resolve_property_with(contexts, field_property!(as_change, message)),
+        "severity" => resolve_property_with(contexts, |vertex| {
+            let change = vertex
+                .as_change()
+                .expect("conversion failed, vertex was not a Change");
+            change.severity.as_str().into()
+        }),
+        _ => {
+            unreachable!("attempted to read unexpected property '{property_name}' on type 'Change'")
+        }
+    }
+}
+
+pub(super) fn resolve_component_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn component(vertex: &Vertex) -> &Component {
+        vertex
+            .as_component()
+            .expect("conversion failed, vertex was not a Component")
+    }
+    match property_name {
+        "kind" => resolve_property_with(contexts, field_property!(as_component, kind)),
+        "name" => resolve_property_with(contexts, field_property!(as_component, name)),
+        "referenceCount" => resolve_property_with(contexts, |vertex| {
+            let component = component(vertex);
+            let document = &component.document;
+            // Security schemes are used by name in security requirements rather than by $ref
+            let count = match (component.kind.as_str(), &component.location) {
+                ("securitySchemes", _) => document.security_requirement_count(&component.name),
+                (_, Some(location)) => document.reference_count(location),
+                (_, None) => 0,
+            };
+            (count as i64).into()
+        }),
+        "column" | "line" | "sourceFile" => {
+            let property_name = property_name.to_string();
+            resolve_property_with(contexts, move |vertex| {
+                let component = component(vertex);
+                location_property(&component.document, &component.location, &property_name)
+            })
+        }
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Component'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_extension_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "name" => resolve_property_with(contexts, field_property!(as_extension, name)),
+        "valueJson" => resolve_property_with(contexts, |vertex| {
+            let extension = vertex
+                .as_extension()
+                .expect("conversion failed, vertex was not a Extension");
+            extension.item.to_string().into()
+        }),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Extension'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_external_docs_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "description" => {
+            resolve_property_with(contexts, field_property!(as_external_docs, description))
+        }
+        "url" => resolve_property_with(contexts, field_property!(as_external_docs, url)),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'ExternalDocs'"
+            )
         }
     }
 }
+
+pub(super) fn resolve_header_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn header(vertex: &Vertex) -> &Named<Resolved<Header>> {
+        vertex
+            .as_header()
+            .expect("conversion failed, vertex was not a Header")
+    }
+    match property_name {
+        "deprecated" => resolve_property_with(contexts, |vertex| {
+            header(vertex).item.item.deprecated.unwrap_or(false).into()
+        }),
+        "description" => resolve_property_with(contexts, |vertex| {
+            header(vertex).item.item.description.clone().into()
+        }),
+        "name" => resolve_property_with(contexts, |vertex| header(vertex).name.clone().into()),
+        "refName" | "refPath" => {
+            let is_name = property_name == "refName";
+            resolve_property_with(contexts, move |vertex| {
+                reference_property(&header(vertex).item.reference, is_name)
+            })
+        }
+        "required" => {
+            resolve_property_with(contexts, |vertex| header(vertex).item.item.required.into())
+        }
+        _ => {
+            unreachable!("attempted to read unexpected property '{property_name}' on type 'Header'")
+        }
+    }
+}
+
+pub(super) fn resolve_info_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "description" => resolve_property_with(contexts, field_property!(as_info, description)),
+        "title" => resolve_property_with(contexts, field_property!(as_info, title)),
+        "version" => resolve_property_with(contexts, field_property!(as_info, version)),
+        _ => {
+            unreachable!("attempted to read unexpected property '{property_name}' on type 'Info'")
+        }
+    }
+}
+
+pub(super) fn resolve_json_value_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn value(vertex: &Vertex) -> &Value {
+        &vertex
+            .as_json_value()
+            .expect("conversion failed, vertex was not a JsonValue")
+            .item
+    }
+    match property_name {
+        "boolean" => resolve_property_with(contexts, |vertex| value(vertex).as_bool().into()),
+        "key" => resolve_property_with(contexts, field_property!(as_json_value, name)),
+        "number" => resolve_property_with(contexts, |vertex| {
+            value(vertex)
+                .as_f64()
+                .map_or(FieldValue::Null, FieldValue::Float64)
+        }),
+        "string" => resolve_property_with(contexts, |vertex| value(vertex).as_str().into()),
+        "type" => resolve_property_with(contexts, |vertex| {
+            match value(vertex) {
+                Value::Null => "null",
+                Value::Bool(_) => "boolean",
+                Value::Number(_) => "number",
+                Value::String(_) => "string",
+                Value::Array(_) => "array",
+                Value::Object(_) => "object",
+            }
+            .into()
+        }),
+        "valueJson" => resolve_property_with(contexts, |vertex| value(vertex).to_string().into()),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'JsonValue'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_located_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "column" | "line" | "sourceFile" => {
+            let property_name = property_name.to_string();
+            resolve_property_with(contexts, move |vertex| {
+                let (document, location) = vertex
+                    .as_located()
+                    .expect("conversion failed, vertex was not Located");
+                location_property(document, location, &property_name)
+            })
+        }
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Located'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_link_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn link(vertex: &Vertex) -> &Named<Resolved<Link>> {
+        vertex
+            .as_link()
+            .expect("conversion failed, vertex was not a Link")
+    }
+    match property_name {
+        "description" => resolve_property_with(contexts, |vertex| {
+            link(vertex).item.item.description.clone().into()
+        }),
+        "name" => resolve_property_with(contexts, |vertex| link(vertex).name.clone().into()),
+        "operationId" => {
+            resolve_property_with(contexts, |vertex| match &link(vertex).item.item.operation {
+                LinkOperation::OperationId(id) => id.clone().into(),
+                LinkOperation::OperationRef(_) => FieldValue::Null,
+            })
+        }
+        "operationRef" => {
+            resolve_property_with(contexts, |vertex| match &link(vertex).item.item.operation {
+                LinkOperation::OperationRef(reference) => reference.clone().into(),
+                LinkOperation::OperationId(_) => FieldValue::Null,
+            })
+        }
+        "parametersJson" => resolve_property_with(contexts, |vertex| {
+            Value::Object(
+                link(vertex)
+                    .item
+                    .item
+                    .parameters
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone()))
+                    .collect(),
+            )
+            .to_string()
+            .into()
+        }),
+        "refName" | "refPath" => {
+            let is_name = property_name == "refName";
+            resolve_property_with(contexts, move |vertex| {
+                reference_property(&link(vertex).item.reference, is_name)
+            })
+        }
+        "requestBodyJson" => resolve_property_with(contexts, |vertex| {
+            link(vertex)
+                .item
+                .item
+                .request_body
+                .as_ref()
+                .map(Value::to_string)
+                .into()
+        }),
+        _ => {
+            unreachable!("attempted to read unexpected property '{property_name}' on type 'Link'")
+        }
+    }
+}
+
+pub(super) fn resolve_media_type_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "mediaType" => resolve_property_with(contexts, field_property!(as_media_type, name)),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'MediaType'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_merge_conflict_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn conflict(vertex: &Vertex) -> &MergeConflict {
+        vertex
+            .as_merge_conflict()
+            .expect("conversion failed, vertex was not a MergeConflict")
+    }
+    match property_name {
+        "key" => resolve_property_with(contexts, field_property!(as_merge_conflict, key)),
+        "sourceFile" => resolve_property_with(contexts, |vertex| {
+            conflict(vertex).source_files.last().cloned().into()
+        }),
+        "sourceFiles" => {
+            resolve_property_with(contexts, field_property!(as_merge_conflict, source_files))
+        }
+        "valuesJson" => resolve_property_with(contexts, |vertex| {
+            conflict(vertex)
+                .values
+                .iter()
+                .map(Value::to_string)
+                .collect::<Vec<_>>()
+                .into()
+        }),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'MergeConflict'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_oauth_flow_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "authorizationUrl" => {
+            resolve_property_with(contexts, field_property!(as_o_auth_flow, authorization_url))
+        }
+        "refreshUrl" => {
+            resolve_property_with(contexts, field_property!(as_o_auth_flow, refresh_url))
+        }
+        "tokenUrl" => resolve_property_with(contexts, field_property!(as_o_auth_flow, token_url)),
+        "type" => resolve_property_with(contexts, field_property!(as_o_auth_flow, flow_type)),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'OAuthFlow'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_oauth_scope_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "description" => resolve_property_with(contexts, field_property!(as_o_auth_scope, item)),
+        "name" => resolve_property_with(contexts, field_property!(as_o_auth_scope, name)),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'OAuthScope'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_operation_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "deprecated" => resolve_property_with(contexts, |vertex| {
+            let operation = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+            operation.operation.deprecated.into()
+        }),
+        "description" => {
+            resolve_property_with(contexts, field_property!(as_operation, description))
+        }
+        "summary" => resolve_property_with(contexts, field_property!(as_operation, summary)),
+        "method" => resolve_property_with(contexts, field_property!(as_operation, method)),
+        "operationId" => resolve_property_with(contexts, |vertex| {
+            let operation = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+            operation.operation.operation_id.clone().into()
+        }),
+        "public" => resolve_property_with(contexts, |vertex| {
+            let operation = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+            let security = operation.effective_security();
+            (security.is_empty() || security.iter().any(|requirement| requirement.is_empty()))
+                .into()
+        }),
+        "tags" => resolve_property_with(contexts, field_property!(as_operation, tags)),
+        "undeclaredTags" => resolve_property_with(contexts, |vertex| {
+            let operation = vertex
+                .as_operation()
+                .expect("conversion failed, vertex was not a Operation");
+            let declared = &operation.document.openapi.tags;
+            operation
+                .operation
+                .tags
+                .iter()
+                .filter(|name| !declared.iter().any(|tag| tag.name == **name))
+                .cloned()
+                .collect::<Vec<_>>()
+                .into()
+        }),
+        "column" | "line" | "sourceFile" => {
+            let property_name = property_name.to_string();
+            resolve_property_with(contexts, move |vertex| {
+                let operation = vertex
+                    .as_operation()
+                    .expect("conversion failed, vertex was not a Operation");
+                location_property(&operation.document, &operation.location, &property_name)
+            })
+        }
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Operation'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_parameter_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn parameter(vertex: &Vertex) -> &Resolved<Parameter> {
+        vertex
+            .as_parameter()
+            .expect("conversion failed, vertex was not a Parameter")
+    }
+    match property_name {
+        "deprecated" => resolve_property_with(contexts, |vertex| {
+            let parameter = parameter(vertex);
+            parameter
+                .item
+                .parameter_data_ref()
+                .deprecated
+                .unwrap_or(false)
+                .into()
+        }),
+        "description" => resolve_property_with(contexts, |vertex| {
+            let parameter = parameter(vertex);
+            parameter
+                .item
+                .parameter_data_ref()
+                .description
+                .clone()
+                .into()
+        }),
+        "explode" => resolve_property_with(contexts, |vertex| {
+            let parameter = parameter(vertex);
+            let style = parameter_style(&parameter.item);
+            parameter
+                .item
+                .parameter_data_ref()
+                .explode
+                .unwrap_or(style == "form")
+                .into()
+        }),
+        "in" => resolve_property_with(contexts, |vertex| {
+            parameter_location(&parameter(vertex).item).into()
+        }),
+        "name" => resolve_property_with(contexts, |vertex| {
+            parameter(vertex)
+                .item
+                .parameter_data_ref()
+                .name
+                .clone()
+                .into()
+        }),
+        "refName" | "refPath" => {
+            let is_name = property_name == "refName";
+            resolve_property_with(contexts, move |vertex| {
+                reference_property(&parameter(vertex).reference, is_name)
+            })
+        }
+        "required" => resolve_property_with(contexts, |vertex| {
+            parameter(vertex).item.parameter_data_ref().required.into()
+        }),
+        "style" => resolve_property_with(contexts, |vertex| {
+            parameter_style(&parameter(vertex).item).into()
+        }),
+        "column" | "line" | "sourceFile" => {
+            let property_name = property_name.to_string();
+            resolve_property_with(contexts, move |vertex| {
+                let parameter = parameter(vertex);
+                location_property(&parameter.document, &parameter.location, &property_name)
+            })
+        }
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Parameter'"
+            )
+        }
+    }
+}
+
+/// The serialized name of a parameter's style, e.g. `form`
+fn parameter_style(parameter: &Parameter) -> String {
+    let style = match parameter {
+        Parameter::Query { style, .. } => serde_json::to_value(style),
+        Parameter::Header { style, .. } => serde_json::to_value(style),
+        Parameter::Path { style, .. } => serde_json::to_value(style),
+        Parameter::Cookie { style, .. } => serde_json::to_value(style),
+    };
+    style
+        .ok()
+        .and_then(|style| style.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+pub(super) fn resolve_path_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "path" => resolve_property_with(contexts, field_property!(as_path, path)),
+        "refName" => resolve_property_with(contexts, |vertex: &Vertex| {
+            let route = vertex
+                .as_path()
+                .expect("conversion failed, vertex was not a Path");
+            route.reference.as_deref().map(ref_name).into()
+        }),
+        "refPath" => resolve_property_with(contexts, field_property!(as_path, reference)),
+        "refUnresolved" => resolve_property_with(contexts, field_property!(as_path, unresolved)),
+        "column" | "line" | "sourceFile" => {
+            let property_name = property_name.to_string();
+            resolve_property_with(contexts, move |vertex: &Vertex| {
+                let route = vertex
+                    .as_path()
+                    .expect("conversion failed, vertex was not a Path");
+                location_property(&route.document, &route.location, &property_name)
+            })
+        }
+        _ => {
+            unreachable!("attempted to read unexpected property '{property_name}' on type 'Path'")
+        }
+    }
+}
+
+pub(super) fn resolve_request_body_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn request_body(vertex: &Vertex) -> &Resolved<RequestBody> {
+        vertex
+            .as_request_body()
+            .expect("conversion failed, vertex was not a RequestBody")
+    }
+    match property_name {
+        "description" => resolve_property_with(contexts, |vertex| {
+            request_body(vertex).item.description.clone().into()
+        }),
+        "refName" | "refPath" => {
+            let is_name = property_name == "refName";
+            resolve_property_with(contexts, move |vertex| {
+                reference_property(&request_body(vertex).reference, is_name)
+            })
+        }
+        "required" => {
+            resolve_property_with(contexts, |vertex| request_body(vertex).item.required.into())
+        }
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'RequestBody'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_response_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn response(vertex: &Vertex) -> &Named<Resolved<Response>> {
+        vertex
+            .as_response()
+            .expect("conversion failed, vertex was not a Response")
+    }
+    match property_name {
+        "description" => resolve_property_with(contexts, |vertex| {
+            response(vertex).item.item.description.clone().into()
+        }),
+        "refName" | "refPath" => {
+            let is_name = property_name == "refName";
+            resolve_property_with(contexts, move |vertex| {
+                reference_property(&response(vertex).item.reference, is_name)
+            })
+        }
+        "statusCode" => {
+            resolve_property_with(contexts, |vertex| response(vertex).name.clone().into())
+        }
+        "column" | "line" | "sourceFile" => {
+            let property_name = property_name.to_string();
+            resolve_property_with(contexts, move |vertex| {
+                let response = &response(vertex).item;
+                location_property(&response.document, &response.location, &property_name)
+            })
+        }
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'Response'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_schema_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn schema(vertex: &Vertex) -> &SchemaNode {
+        vertex
+            .as_schema()
+            .expect("conversion failed, vertex was not a Schema")
+    }
+    match property_name {
+        "description" | "format" | "pattern" | "type" => {
+            let keyword = property_name.to_string();
+            resolve_property_with(contexts, move |vertex| {
+                schema(vertex)
+                    .json
+                    .get(&keyword)
+                    .and_then(Value::as_str)
+                    .into()
+            })
+        }
+        "enum" => resolve_property_with(contexts, |vertex| {
+            let values = schema(vertex).json.get("enum").and_then(Value::as_array);
+            values
+                .map(|values| {
+                    values
+                        .iter()
+                        .map(|value| match value {
+                            Value::String(value) => value.clone(),
+                            value => value.to_string(),
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .into()
+        }),
+        "exclusiveMaximum" | "exclusiveMinimum" | "nullable" => {
+            let keyword = property_name.to_string();
+            resolve_property_with(contexts, move |vertex| {
+                schema(vertex)
+                    .json
+                    .get(&keyword)
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false)
+                    .into()
+            })
+        }
+        "maximum" | "minimum" | "multipleOf" => {
+            let keyword = property_name.to_string();
+            resolve_property_with(contexts, move |vertex| {
+                schema(vertex)
+                    .json
+                    .get(&keyword)
+                    .and_then(Value::as_f64)
+                    .map_or(FieldValue::Null, FieldValue::Float64)
+            })
+        }
+        "maxLength" | "minLength" => {
+            let keyword = property_name.to_string();
+            resolve_property_with(contexts, move |vertex| {
+                schema(vertex)
+                    .json
+                    .get(&keyword)
+                    .and_then(Value::as_u64)
+                    .into()
+            })
+        }
+        "name" => resolve_property_with(contexts, |vertex| {
+            let schema = schema(vertex);
+            schema
+                .name
+                .clone()
+                .or_else(|| schema.schema.reference.as_deref().map(ref_name))
+                .into()
+        }),
+        "referenceCount" => resolve_property_with(contexts, |vertex| {
+            let schema = schema(vertex);
+            let location = schema.location.as_deref();
+            let count = location.map_or(0, |location| {
+                schema.schema.document.reference_count(location)
+            });
+            (count as i64).into()
+        }),
+        "refName" | "refPath" => {
+            let is_name = property_name == "refName";
+            resolve_property_with(contexts, move |vertex| {
+                reference_property(&schema(vertex).schema.reference, is_name)
+            })
+        }
+        "required" => resolve_property_with(contexts, |vertex| {
+            let required = schema(vertex)
+                .json
+                .get("required")
+                .and_then(Value::as_array);
+            required
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+                .into()
+        }),
+        "column" | "line" | "sourceFile" => {
+            let property_name = property_name.to_string();
+            resolve_property_with(contexts, move |vertex| {
+                let schema = schema(vertex);
+                location_property(&schema.schema.document, &schema.location, &property_name)
+            })
+        }
+        _ => {
+            unreachable!("attempted to read unexpected property '{property_name}' on type 'Schema'")
+        }
+    }
+}
+
+pub(super) fn resolve_schema_property_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "name" => resolve_property_with(contexts, field_property!(as_schema_property, name)),
+        "required" => {
+            resolve_property_with(contexts, field_property!(as_schema_property, required))
+        }
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'SchemaProperty'"
+            )
+        }
+    }
+}
+
+/// Properties of the `SecurityScheme` interface and the types implementing it
+pub(super) fn resolve_security_scheme_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    type_name: &str,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn scheme(vertex: &Vertex) -> &Named<Resolved<SecurityScheme>> {
+        vertex
+            .as_any_security_scheme()
+            .expect("conversion failed, vertex was not a SecurityScheme")
+    }
+    match property_name {
+        "bearerFormat" => {
+            resolve_property_with(contexts, |vertex| match &scheme(vertex).item.item {
+                SecurityScheme::HTTP { bearer_format, .. } => bearer_format.clone().into(),
+                _ => FieldValue::Null,
+            })
+        }
+        "description" => {
+            resolve_property_with(contexts, |vertex| match &scheme(vertex).item.item {
+                SecurityScheme::APIKey { description, .. }
+                | SecurityScheme::HTTP { description, .. }
+                | SecurityScheme::OAuth2 { description, .. }
+                | SecurityScheme::OpenIDConnect { description, .. } => description.clone().into(),
+            })
+        }
+        "in" => resolve_property_with(contexts, |vertex| match &scheme(vertex).item.item {
+            SecurityScheme::APIKey { location, .. } => match location {
+                APIKeyLocation::Query => "query".into(),
+                APIKeyLocation::Header => "header".into(),
+                APIKeyLocation::Cookie => "cookie".into(),
+            },
+            _ => FieldValue::Null,
+        }),
+        "name" => resolve_property_with(contexts, |vertex| scheme(vertex).name.clone().into()),
+        "sourceFile" => resolve_property_with(contexts, |vertex| {
+            let scheme = &scheme(vertex).item;
+            location_property(&scheme.document, &scheme.location, "sourceFile")
+        }),
+        "openIdConnectUrl" => {
+            resolve_property_with(contexts, |vertex| match &scheme(vertex).item.item {
+                SecurityScheme::OpenIDConnect {
+                    open_id_connect_url,
+                    ..
+                } => open_id_connect_url.clone().into(),
+                _ => FieldValue::Null,
+            })
+        }
+        "parameterName" => {
+            resolve_property_with(contexts, |vertex| match &scheme(vertex).item.item {
+                SecurityScheme::APIKey { name, .. } => name.clone().into(),
+                _ => FieldValue::Null,
+            })
+        }
+        "refName" | "refPath" => {
+            let is_name = property_name == "refName";
+            resolve_property_with(contexts, move |vertex| {
+                reference_property(&scheme(vertex).item.reference, is_name)
+            })
+        }
+        "scheme" => resolve_property_with(contexts, |vertex| match &scheme(vertex).item.item {
+            SecurityScheme::HTTP { scheme, .. } => scheme.clone().into(),
+            _ => FieldValue::Null,
+        }),
+        "type" => resolve_property_with(contexts, |vertex| {
+            match &scheme(vertex).item.item {
+                SecurityScheme::APIKey { .. } => "apiKey",
+                SecurityScheme::HTTP { .. } => "http",
+                SecurityScheme::OAuth2 { .. } => "oauth2",
+                SecurityScheme::OpenIDConnect { .. } => "openIdConnect",
+            }
+            .into()
+        }),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type '{type_name}'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_security_scheme_requirement_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "name" => resolve_property_with(
+            contexts,
+            field_property!(as_security_scheme_requirement, name),
+        ),
+        "scopes" => resolve_property_with(
+            contexts,
+            field_property!(as_security_scheme_requirement, scopes),
+        ),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'SecuritySchemeRequirement'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_server_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "description" => resolve_property_with(contexts, field_property!(as_server, description)),
+        "expandedUrl" => resolve_property_with(contexts, |vertex| {
+            let server = vertex
+                .as_server()
+                .expect("conversion failed, vertex was not a Server");
+            expand_server_url(server).into()
+        }),
+        "url" => resolve_property_with(contexts, field_property!(as_server, url)),
+        _ => {
+            unreachable!("attempted to read unexpected property '{property_name}' on type 'Server'")
+        }
+    }
+}
+
+pub(super) fn resolve_server_variable_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn variable(vertex: &Vertex) -> &Named<ServerVariable> {
+        vertex
+            .as_server_variable()
+            .expect("conversion failed, vertex was not a ServerVariable")
+    }
+    match property_name {
+        "default" => resolve_property_with(contexts, |vertex| {
+            variable(vertex).item.default.clone().into()
+        }),
+        "description" => resolve_property_with(contexts, |vertex| {
+            variable(vertex).item.description.clone().into()
+        }),
+        "enum" => resolve_property_with(contexts, |vertex| {
+            let enumeration = &variable(vertex).item.enumeration;
+            if enumeration.is_empty() {
+                FieldValue::Null
+            } else {
+                enumeration.clone().into()
+            }
+        }),
+        "name" => resolve_property_with(contexts, field_property!(as_server_variable, name)),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'ServerVariable'"
+            )
+        }
+    }
+}
+
+pub(super) fn resolve_tag_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    fn tag(vertex: &Vertex) -> &Resolved<Tag> {
+        vertex
+            .as_tag()
+            .expect("conversion failed, vertex was not a Tag")
+    }
+    match property_name {
+        "description" => resolve_property_with(contexts, |vertex| {
+            tag(vertex).item.description.clone().into()
+        }),
+        "name" => resolve_property_with(contexts, |vertex| tag(vertex).item.name.clone().into()),
+        "column" | "line" | "sourceFile" => {
+            let property_name = property_name.to_string();
+            resolve_property_with(contexts, move |vertex| {
+                let tag = tag(vertex);
+                location_property(&tag.document, &tag.location, &property_name)
+            })
+        }
+        _ => {
+            unreachable!("attempted to read unexpected property '{property_name}' on type 'Tag'")
+        }
+    }
+}
+
+pub(super) fn resolve_validation_error_property<'a, V: AsVertex<Vertex> + 'a>(
+    contexts: ContextIterator<'a, V>,
+    property_name: &str,
+    _resolve_info: &ResolveInfo,
+) -> ContextOutcomeIterator<'a, V, FieldValue> {
+    match property_name {
+        "instancePath" => resolve_property_with(
+            contexts,
+            field_property!(as_validation_error, instance_path),
+        ),
+        "keyword" => resolve_property_with(contexts, field_property!(as_validation_error, keyword)),
+        "message" => resolve_property_with(contexts, field_property!(as_validation_error, message)),
+        _ => {
+            unreachable!(
+                "attempted to read unexpected property '{property_name}' on type 'ValidationError'"
+            )
+        }
+    }
+}
+
+/// `sourceFile`, `line` or `column` of a vertex at a location
+fn location_property(
+    document: &Document,
+    location: &Option<String>,
+    property_name: &str,
+) -> FieldValue {
+    let Some(location) = location else {
+        return FieldValue::Null;
+    };
+    match property_name {
+        "sourceFile" => document.source_file_of(location).into(),
+        "line" => document
+            .position_of(location)
+            .map(|position| position.line as i64)
+            .into(),
+        _ => document
+            .position_of(location)
+            .map(|position| position.column as i64)
+            .into(),
+    }
+}
+
+/// `refName` or `refPath` of a vertex that may have been reached through a `$ref`
+fn reference_property(reference: &Option<String>, is_name: bool) -> FieldValue {
+    match reference {
+        Some(reference) if is_name => ref_name(reference).into(),
+        Some(reference) => reference.clone().into(),
+        None => FieldValue::Null,
+    }
+}
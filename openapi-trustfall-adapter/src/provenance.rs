@@ -0,0 +1,143 @@
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::refs::escape;
+
+/// Methods that an operation can be defined for in a path item
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Swagger 2.0 sections that become components once the document is converted
+pub(crate) const SWAGGER_COMPONENTS: [(&str, &str); 4] = [
+    ("definitions", "schemas"),
+    ("parameters", "parameters"),
+    ("responses", "responses"),
+    ("securityDefinitions", "securitySchemes"),
+];
+
+/// Which file each part of a merged document came from
+#[derive(Debug, Clone, Default)]
+pub struct Provenance {
+    /// The file that defined each path, operation, component and tag, by JSON pointer. When
+    /// several files define one, it's the last of them, since its values are the ones merged in
+    sources: IndexMap<String, String>,
+    /// Keys that more than one file defines with different values
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// A key that more than one file defines with different values, so all but the last file's
+/// value were overwritten when the files were merged
+#[derive(Debug, Clone)]
+pub struct MergeConflict {
+    /// JSON pointer of the key, e.g. `/paths/~1users/get/summary`
+    pub key: String,
+    /// Files that define the key, in the order they were merged
+    pub source_files: Vec<String>,
+    /// The value from each file
+    pub values: Vec<Value>,
+}
+
+impl Provenance {
+    /// Tracks the files that are merged, keyed by their path relative to the spec, in the order
+    /// they're merged in
+    pub(crate) fn new(parts: &[(String, Value)]) -> Self {
+        let mut sources = IndexMap::new();
+        let mut leaves: IndexMap<String, Vec<(&str, &Value)>> = IndexMap::new();
+        for (file, value) in parts {
+            for pointer in tracked_pointers(value) {
+                sources.insert(pointer, file.clone());
+            }
+            let mut values = Vec::new();
+            collect_leaves(value, String::new(), &mut values);
+            for (pointer, value) in values {
+                leaves.entry(pointer).or_default().push((file, value));
+            }
+        }
+        let conflicts = leaves
+            .into_iter()
+            .filter(|(_, values)| values.iter().any(|(_, value)| *value != values[0].1))
+            .map(|(key, values)| MergeConflict {
+                key,
+                source_files: values.iter().map(|(file, _)| file.to_string()).collect(),
+                values: values.into_iter().map(|(_, value)| value.clone()).collect(),
+            })
+            .collect();
+        Self { sources, conflicts }
+    }
+
+    /// Whether any of the document was merged from the file
+    pub(crate) fn contains_file(&self, file: &str) -> bool {
+        self.sources.values().any(|source| source == file)
+    }
+
+    /// The file that defined the item at the pointer, or the item it's within, e.g. the
+    /// operation for `/paths/~1users/get/responses`
+    pub(crate) fn source_file(&self, pointer: &str) -> Option<&str> {
+        let mut pointer = pointer;
+        loop {
+            if let Some(file) = self.sources.get(pointer) {
+                return Some(file);
+            }
+            pointer = pointer.rsplit_once('/')?.0;
+        }
+    }
+}
+
+/// Pointers to the paths, operations, components and tags a file defines
+fn tracked_pointers(value: &Value) -> Vec<String> {
+    let mut pointers = Vec::new();
+    for (path, item) in entries(value, "paths") {
+        let pointer = format!("/paths/{}", escape(path));
+        for method in METHODS {
+            if item.get(method).is_some() {
+                pointers.push(format!("{pointer}/{method}"));
+            }
+        }
+        pointers.push(pointer);
+    }
+    for (name, _) in entries(value, "webhooks") {
+        pointers.push(format!("/webhooks/{}", escape(name)));
+    }
+    for (kind, components) in entries(value, "components") {
+        for (name, _) in components.as_object().into_iter().flatten() {
+            pointers.push(format!("/components/{kind}/{}", escape(name)));
+        }
+    }
+    if value.get("swagger").is_some() {
+        for (section, kind) in SWAGGER_COMPONENTS {
+            for (name, _) in entries(value, section) {
+                pointers.push(format!("/components/{kind}/{}", escape(name)));
+            }
+        }
+    }
+    let tags = value.get("tags").and_then(Value::as_array);
+    for tag in tags.into_iter().flatten() {
+        if let Some(name) = tag.get("name").and_then(Value::as_str) {
+            pointers.push(format!("/tags/{}", escape(name)));
+        }
+    }
+    pointers
+}
+
+/// The entries of an object within the value
+fn entries<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = (&'a String, &'a Value)> {
+    value
+        .get(key)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+}
+
+/// Every value that's replaced rather than merged when files are merged, i.e. everything that
+/// isn't an object
+fn collect_leaves<'a>(value: &'a Value, pointer: String, leaves: &mut Vec<(String, &'a Value)>) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                collect_leaves(value, format!("{pointer}/{}", escape(key)), leaves);
+            }
+        }
+        _ => leaves.push((pointer, value)),
+    }
+}
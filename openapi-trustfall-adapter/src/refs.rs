@@ -0,0 +1,244 @@
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::{document::Document, errors::OpenAPIAdapterErrors, utils::open_file};
+
+/// Keys whose values are literal data, so a `$ref` key inside them isn't a reference
+const DATA_KEYS: [&str; 4] = ["example", "default", "enum", "const"];
+
+/// An item that may have been reached through one or more `$ref`s
+#[derive(Debug, Clone)]
+pub struct Resolved<T> {
+    pub item: T,
+    /// The first reference that was followed, e.g. `#/components/schemas/User`
+    pub reference: Option<String>,
+    /// The document the item is from, which its own references are resolved against
+    pub document: Arc<Document>,
+    /// Where the item is defined, e.g. `#/paths/~1users/get/parameters/0`, when it's known.
+    /// For an item reached through references, it's the last reference
+    pub location: Option<String>,
+}
+
+impl<T> Resolved<T> {
+    /// Sets where the item is, unless it was reached through a reference
+    pub(crate) fn at(mut self, location: Option<String>) -> Self {
+        self.location = self.location.or(location);
+        self
+    }
+}
+
+/// The location of an item within another, e.g. `#/paths/~1users/get/responses/200` for
+/// the `200` response of `#/paths/~1users/get`
+pub(crate) fn join(location: Option<&str>, keys: &[&str]) -> Option<String> {
+    let mut location = location?.to_string();
+    for key in keys {
+        location.push('/');
+        location.push_str(&escape(key));
+    }
+    Some(location)
+}
+
+/// The last segment of a reference's JSON pointer, e.g. `User` for `#/components/schemas/User`
+pub(crate) fn ref_name(reference: &str) -> String {
+    let (file, pointer) = split(reference);
+    match pointer.rsplit_once('/') {
+        Some((_, name)) => name.replace("~1", "/").replace("~0", "~"),
+        None => file.rsplit('/').next().unwrap_or(file).to_string(),
+    }
+}
+
+/// Escapes a key to be a segment of a JSON pointer, e.g. `~1users` for `/users`
+pub(crate) fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Splits a reference into the file it points to (empty for the current document) and the
+/// JSON pointer within that file
+pub(crate) fn split(reference: &str) -> (&str, &str) {
+    reference.split_once('#').unwrap_or((reference, ""))
+}
+
+/// Counts the `$ref`s within a value by what they point to
+pub(crate) fn count_references(value: &Value, counts: &mut HashMap<String, usize>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::String(reference)) = object.get("$ref") {
+                *counts.entry(reference.clone()).or_default() += 1;
+            }
+            for (key, value) in object {
+                if !DATA_KEYS.contains(&key.as_str()) {
+                    count_references(value, counts);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                count_references(value, counts);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Every `$ref` within a value, along with the JSON pointer of the object it's in
+pub(crate) fn find_references(value: &Value, pointer: String, found: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::String(reference)) = object.get("$ref") {
+                found.push((pointer.clone(), reference.clone()));
+            }
+            for (key, value) in object {
+                if !DATA_KEYS.contains(&key.as_str()) {
+                    find_references(value, format!("{pointer}/{}", escape(key)), found);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for (i, value) in values.iter().enumerate() {
+                find_references(value, format!("{pointer}/{i}"), found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// What a `$ref` is used as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Target {
+    Callback,
+    Example,
+    Header,
+    Link,
+    Parameter,
+    PathItem,
+    RequestBody,
+    Response,
+    Schema,
+    SecurityScheme,
+}
+
+/// What a `$ref` at the pointer is used as, going by the keys it's under, e.g. a schema for
+/// `/paths/~1users/get/responses/200/content/application~1json/schema`
+pub(crate) fn reference_target(pointer: &str) -> Option<Target> {
+    let mut keys = pointer.rsplit('/');
+    let key = keys.next()?;
+    let parent = keys.next().unwrap_or_default();
+    // Properties can have any name, so they're checked before the other keys
+    let target = match (parent, key) {
+        ("properties" | "schemas" | "allOf" | "oneOf" | "anyOf", _)
+        | (_, "schema" | "items" | "not" | "additionalProperties") => Target::Schema,
+        ("paths" | "webhooks" | "pathItems", _) => Target::PathItem,
+        ("parameters", _) => Target::Parameter,
+        ("responses", _) => Target::Response,
+        ("requestBodies", _) | (_, "requestBody") => Target::RequestBody,
+        ("headers", _) => Target::Header,
+        ("examples", _) => Target::Example,
+        ("links", _) => Target::Link,
+        ("callbacks", _) => Target::Callback,
+        ("securitySchemes", _) => Target::SecurityScheme,
+        _ => return None,
+    };
+    Some(target)
+}
+
+/// Loads the files that a spec file references with relative `$ref`s, e.g.
+/// `./schemas/user.yaml#/User`.
+///
+/// References to other files are rewritten to be relative to `root`, so that they stay
+/// unique once files from different directories are merged, and each referenced file is
+/// added to `external` under that key. A file is only loaded once, so files that reference
+/// each other don't loop. References to files that are missing or can't be parsed are left
+/// as they are, to be reported as unresolved.
+pub(crate) fn bundle(
+    value: &mut Value,
+    file: &Path,
+    root: &Path,
+    external: &mut IndexMap<String, Value>,
+) {
+    bundle_value(value, file, None, root, external)
+}
+
+fn bundle_value(
+    value: &mut Value,
+    file: &Path,
+    current_key: Option<&str>,
+    root: &Path,
+    external: &mut IndexMap<String, Value>,
+) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::String(reference)) = object.get_mut("$ref") {
+                *reference = bundle_reference(reference, file, current_key, root, external);
+            }
+            for (key, value) in object.iter_mut() {
+                if !DATA_KEYS.contains(&key.as_str()) {
+                    bundle_value(value, file, current_key, root, external);
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                bundle_value(value, file, current_key, root, external);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn bundle_reference(
+    reference: &str,
+    file: &Path,
+    current_key: Option<&str>,
+    root: &Path,
+    external: &mut IndexMap<String, Value>,
+) -> String {
+    let (target, pointer) = split(reference);
+    if target.contains("://") {
+        return reference.to_string();
+    }
+    if target.is_empty() {
+        // Local references within a referenced file point into that file
+        return match current_key {
+            Some(key) => format!("{key}#{pointer}"),
+            None => reference.to_string(),
+        };
+    }
+    let target = file.parent().unwrap_or(Path::new(".")).join(target);
+    let Ok(key) = file_key(&target, root) else {
+        return reference.to_string();
+    };
+    if !external.contains_key(&key) {
+        let contents = open_file(target.clone()).ok();
+        let Some(mut value) = contents.and_then(|contents| serde_yaml::from_str(&contents).ok())
+        else {
+            return reference.to_string();
+        };
+        // Reserve the key before walking the file, in case it references itself
+        external.insert(key.clone(), Value::Null);
+        bundle_value(&mut value, &target, Some(&key), root, external);
+        external.insert(key.clone(), value);
+    }
+    format!("{key}#{pointer}")
+}
+
+/// The path of a file relative to the root directory with `/` separators, or the absolute
+/// path when the file is outside of the root directory
+pub(crate) fn file_key(file: &Path, root: &Path) -> Result<String, OpenAPIAdapterErrors> {
+    let file = file
+        .canonicalize()
+        .map_err(|_| OpenAPIAdapterErrors::PathDoesNotExist(file.to_path_buf()))?;
+    let relative = root
+        .canonicalize()
+        .ok()
+        .and_then(|root| file.strip_prefix(root).ok().map(Path::to_path_buf));
+    Ok(match relative {
+        Some(relative) => relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        None => file.to_string_lossy().to_string(),
+    })
+}
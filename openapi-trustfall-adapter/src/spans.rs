@@ -0,0 +1,184 @@
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use yaml_rust2::{
+    parser::{Event, MarkedEventReceiver, Parser},
+    scanner::Marker,
+};
+
+use crate::{
+    provenance::SWAGGER_COMPONENTS,
+    refs::{escape, file_key},
+    utils::{open_file, ExternalFiles},
+};
+
+/// Pointer for what's within a key that isn't a string, which nothing looks up
+const COMPLEX_KEY: &str = "#";
+
+/// Where each value is in a file, by JSON pointer
+pub type Positions = IndexMap<String, Position>;
+
+/// A line and column in a source file, both starting from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Where the values are in the files and the files they reference, keyed by their path relative
+/// to the root. The files are read again, since the merged document doesn't keep their layout.
+pub(crate) fn file_positions(
+    files: &[PathBuf],
+    root: &Path,
+    external: &ExternalFiles,
+) -> IndexMap<String, Positions> {
+    let files = files
+        .iter()
+        .filter_map(|file| Some((file_key(file, root).ok()?, file.clone())));
+    let external = external.keys().map(|key| (key.clone(), root.join(key)));
+    files
+        .chain(external)
+        .filter_map(|(key, file)| Some((key, positions(&open_file(file).ok()?))))
+        .collect()
+}
+
+/// Where each value in a YAML or JSON document is, by JSON pointer. Values in a mapping are at
+/// their key. Nothing is found for a document that can't be parsed.
+pub(crate) fn positions(contents: &str) -> Positions {
+    let mut receiver = Receiver::default();
+    if Parser::new(contents.chars())
+        .load(&mut receiver, false)
+        .is_err()
+    {
+        return Positions::new();
+    }
+    let mut positions = receiver.positions;
+    // Swagger 2.0 sections are moved to components when the document is converted
+    if positions.contains_key("/swagger") {
+        for (section, kind) in SWAGGER_COMPONENTS {
+            let prefix = format!("/{section}/");
+            let moved: Vec<_> = positions
+                .iter()
+                .filter_map(|(pointer, position)| {
+                    let rest = pointer.strip_prefix(&prefix)?;
+                    Some((format!("/components/{kind}/{rest}"), *position))
+                })
+                .collect();
+            positions.extend(moved);
+        }
+    }
+    positions
+}
+
+enum Frame {
+    /// A mapping, and the key whose value is being read
+    Mapping {
+        pointer: String,
+        key: Option<String>,
+    },
+    /// A sequence, and the index of the item being read
+    Sequence { pointer: String, index: usize },
+}
+
+#[derive(Default)]
+struct Receiver {
+    positions: Positions,
+    stack: Vec<Frame>,
+    /// A mapping that isn't at a key, which is moved to its first key once it's read, since the
+    /// parser marks block mappings at their first `:`
+    unplaced: Option<String>,
+}
+
+impl Receiver {
+    /// Starts a value, giving its pointer, or `None` when it's a mapping's key
+    fn start_value(&mut self, event: &Event, mark: Marker) -> Option<String> {
+        let position = Position {
+            line: mark.line(),
+            column: mark.col() + 1,
+        };
+        let pointer = match self.stack.last_mut() {
+            None => String::new(),
+            Some(Frame::Mapping { pointer, key }) => match key {
+                Some(key) => format!("{pointer}/{}", escape(key)),
+                None => {
+                    if let Some(mapping) = self.unplaced.take() {
+                        self.positions.insert(mapping, position);
+                    }
+                    let Event::Scalar(name, ..) = event else {
+                        // Keys that aren't strings can't be pointed to
+                        *key = Some(String::new());
+                        return None;
+                    };
+                    let pointer = format!("{pointer}/{}", escape(name));
+                    self.positions.insert(pointer, position);
+                    *key = Some(name.clone());
+                    return None;
+                }
+            },
+            Some(Frame::Sequence { pointer, index }) => format!("{pointer}/{index}"),
+        };
+        if matches!(event, Event::MappingStart(..)) && !self.positions.contains_key(&pointer) {
+            self.unplaced = Some(pointer.clone());
+        }
+        self.positions.entry(pointer.clone()).or_insert(position);
+        Some(pointer)
+    }
+
+    /// Moves on from a value that has been read
+    fn end_value(&mut self) {
+        match self.stack.last_mut() {
+            Some(Frame::Mapping { key, .. }) => *key = None,
+            Some(Frame::Sequence { index, .. }) => *index += 1,
+            None => {}
+        }
+    }
+}
+
+impl MarkedEventReceiver for Receiver {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        match &event {
+            Event::Scalar(value, ..) => {
+                if let Some(pointer) = self.start_value(&event, mark) {
+                    // Tags are found by name rather than by their index in the list
+                    if let Some(tag) = pointer
+                        .strip_suffix("/name")
+                        .filter(|tag| is_tag(tag))
+                        .and_then(|tag| self.positions.get(tag).copied())
+                    {
+                        self.positions
+                            .insert(format!("/tags/{}", escape(value)), tag);
+                    }
+                    self.end_value();
+                }
+            }
+            Event::Alias(_) => {
+                let pointer = self.start_value(&event, mark);
+                if pointer.is_some() {
+                    self.end_value();
+                }
+            }
+            Event::MappingStart(..) => {
+                let pointer = self.start_value(&event, mark);
+                let pointer = pointer.unwrap_or_else(|| COMPLEX_KEY.to_string());
+                self.stack.push(Frame::Mapping { pointer, key: None });
+            }
+            Event::SequenceStart(..) => {
+                let pointer = self.start_value(&event, mark);
+                let pointer = pointer.unwrap_or_else(|| COMPLEX_KEY.to_string());
+                self.stack.push(Frame::Sequence { pointer, index: 0 });
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.stack.pop();
+                self.end_value();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether the pointer is to an item of the document's `tags`, e.g. `/tags/0`
+fn is_tag(pointer: &str) -> bool {
+    pointer
+        .strip_prefix("/tags/")
+        .is_some_and(|index| index.parse::<usize>().is_ok())
+}
@@ -0,0 +1,537 @@
+use serde_json::{json, Map, Value};
+
+/// The version the converted documents declare
+const CONVERTED_VERSION: &str = "3.0.3";
+
+const METHODS: [&str; 7] = ["get", "put", "post", "delete", "options", "head", "patch"];
+
+/// Parameter fields that describe the value and move into `schema` in 3.0
+const SCHEMA_FIELDS: [&str; 16] = [
+    "type",
+    "format",
+    "items",
+    "default",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "enum",
+    "multipleOf",
+];
+
+/// Converts a Swagger 2.0 document into an OpenAPI 3.0 document.
+///
+/// `definitions` become `components.schemas`, body and form parameters become a `requestBody`,
+/// `host`, `basePath` and `schemes` become `servers`, response schemas move into `content` for
+/// every type in `produces`, and `securityDefinitions` become `components.securitySchemes`.
+pub(crate) fn convert(swagger: Value) -> Value {
+    let Value::Object(mut swagger) = swagger else {
+        return swagger;
+    };
+    let consumes = media_types(swagger.get("consumes"));
+    let produces = media_types(swagger.get("produces"));
+    let parameters = swagger
+        .remove("parameters")
+        .and_then(into_object)
+        .unwrap_or_default();
+
+    let mut openapi = Map::new();
+    openapi.insert("openapi".to_string(), json!(CONVERTED_VERSION));
+    if let Some(info) = swagger.remove("info") {
+        openapi.insert("info".to_string(), info);
+    }
+    openapi.insert("servers".to_string(), servers(&swagger));
+
+    let paths = swagger
+        .remove("paths")
+        .and_then(into_object)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(path, item)| {
+            let item = match item {
+                Value::Object(item) => {
+                    Value::Object(convert_path_item(item, &parameters, &consumes, &produces))
+                }
+                item => item,
+            };
+            (path, item)
+        })
+        .collect();
+    openapi.insert("paths".to_string(), Value::Object(paths));
+
+    let mut components = Map::new();
+    if let Some(definitions) = swagger.remove("definitions") {
+        components.insert("schemas".to_string(), definitions);
+    }
+    let mut component_parameters = Map::new();
+    let mut request_bodies = Map::new();
+    for (name, parameter) in &parameters {
+        if is_body_parameter(parameter) {
+            request_bodies.insert(
+                name.clone(),
+                request_body(std::slice::from_ref(parameter), &consumes),
+            );
+        } else {
+            component_parameters.insert(name.clone(), convert_parameter(parameter.clone()));
+        }
+    }
+    if !component_parameters.is_empty() {
+        components.insert(
+            "parameters".to_string(),
+            Value::Object(component_parameters),
+        );
+    }
+    if !request_bodies.is_empty() {
+        components.insert("requestBodies".to_string(), Value::Object(request_bodies));
+    }
+    if let Some(responses) = swagger.remove("responses").and_then(into_object) {
+        let responses = responses
+            .into_iter()
+            .map(|(name, response)| (name, convert_response(response, &produces)))
+            .collect();
+        components.insert("responses".to_string(), Value::Object(responses));
+    }
+    if let Some(schemes) = swagger.remove("securityDefinitions").and_then(into_object) {
+        let schemes = schemes
+            .into_iter()
+            .map(|(name, scheme)| (name, convert_security_scheme(scheme)))
+            .collect();
+        components.insert("securitySchemes".to_string(), Value::Object(schemes));
+    }
+    if !components.is_empty() {
+        openapi.insert("components".to_string(), Value::Object(components));
+    }
+
+    for key in ["security", "tags", "externalDocs"] {
+        if let Some(value) = swagger.remove(key) {
+            openapi.insert(key.to_string(), value);
+        }
+    }
+    for (key, value) in swagger {
+        if key.starts_with("x-") {
+            openapi.insert(key, value);
+        }
+    }
+
+    let mut openapi = Value::Object(openapi);
+    convert_schemas(&mut openapi);
+    rewrite_references(&mut openapi, &parameters);
+    openapi
+}
+
+fn into_object(value: Value) -> Option<Map<String, Value>> {
+    match value {
+        Value::Object(object) => Some(object),
+        _ => None,
+    }
+}
+
+fn media_types(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|types| {
+            types
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .filter(|types: &Vec<String>| !types.is_empty())
+        .unwrap_or_else(|| vec!["application/json".to_string()])
+}
+
+/// One server per scheme built from `host` and `basePath`
+fn servers(swagger: &Map<String, Value>) -> Value {
+    let base_path = swagger
+        .get("basePath")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let servers = match swagger.get("host").and_then(Value::as_str) {
+        Some(host) => {
+            let schemes: Vec<&str> = swagger
+                .get("schemes")
+                .and_then(Value::as_array)
+                .map(|schemes| schemes.iter().filter_map(Value::as_str).collect())
+                .filter(|schemes: &Vec<&str>| !schemes.is_empty())
+                .unwrap_or_else(|| vec!["https"]);
+            schemes
+                .into_iter()
+                .map(|scheme| json!({ "url": format!("{scheme}://{host}{base_path}") }))
+                .collect()
+        }
+        None if !base_path.is_empty() => vec![json!({ "url": base_path })],
+        None => Vec::new(),
+    };
+    Value::Array(servers)
+}
+
+fn convert_path_item(
+    mut item: Map<String, Value>,
+    parameters: &Map<String, Value>,
+    consumes: &[String],
+    produces: &[String],
+) -> Map<String, Value> {
+    // Body and form parameters can't be shared through the path item in 3.0,
+    // so they are pushed down into every operation
+    let (shared_body, shared) = split_parameters(
+        item.remove("parameters")
+            .and_then(|p| match p {
+                Value::Array(p) => Some(p),
+                _ => None,
+            })
+            .unwrap_or_default(),
+        parameters,
+    );
+    if !shared.is_empty() {
+        item.insert("parameters".to_string(), Value::Array(shared));
+    }
+    for method in METHODS {
+        if let Some(Value::Object(operation)) = item.remove(method) {
+            let operation =
+                convert_operation(operation, &shared_body, parameters, consumes, produces);
+            item.insert(method.to_string(), Value::Object(operation));
+        }
+    }
+    item
+}
+
+fn convert_operation(
+    mut operation: Map<String, Value>,
+    shared_body: &[Value],
+    parameters: &Map<String, Value>,
+    consumes: &[String],
+    produces: &[String],
+) -> Map<String, Value> {
+    let consumes = match operation.remove("consumes") {
+        Some(value) => media_types(Some(&value)),
+        None => consumes.to_vec(),
+    };
+    let produces = match operation.remove("produces") {
+        Some(value) => media_types(Some(&value)),
+        None => produces.to_vec(),
+    };
+    operation.remove("schemes");
+
+    let (mut body, other) = split_parameters(
+        match operation.remove("parameters") {
+            Some(Value::Array(parameters)) => parameters,
+            _ => Vec::new(),
+        },
+        parameters,
+    );
+    if body.is_empty() {
+        body = shared_body.to_vec();
+    }
+    if !other.is_empty() {
+        operation.insert("parameters".to_string(), Value::Array(other));
+    }
+    // A shared body parameter is converted into a shared request body
+    let shared_reference = match body.as_slice() {
+        [parameter] => parameter.get("$ref").cloned(),
+        _ => None,
+    };
+    match shared_reference {
+        Some(reference) => {
+            operation.insert("requestBody".to_string(), json!({ "$ref": reference }));
+        }
+        None if !body.is_empty() => {
+            operation.insert("requestBody".to_string(), request_body(&body, &consumes));
+        }
+        None => {}
+    }
+
+    if let Some(Value::Object(responses)) = operation.remove("responses") {
+        let responses = responses
+            .into_iter()
+            .map(|(code, response)| (code, convert_response(response, &produces)))
+            .collect();
+        operation.insert("responses".to_string(), Value::Object(responses));
+    }
+    operation
+}
+
+fn is_body_parameter(parameter: &Value) -> bool {
+    matches!(
+        parameter.get("in").and_then(Value::as_str),
+        Some("body" | "formData")
+    )
+}
+
+/// Splits parameters into body/form parameters and everything else, converting the latter.
+/// References to shared body parameters are kept as references so they can become a
+/// `requestBodies` reference.
+fn split_parameters(list: Vec<Value>, parameters: &Map<String, Value>) -> (Vec<Value>, Vec<Value>) {
+    let mut body = Vec::new();
+    let mut other = Vec::new();
+    for parameter in list {
+        let referenced = parameter
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix("#/parameters/"))
+            .and_then(|name| parameters.get(name));
+        match referenced {
+            Some(referenced) if is_body_parameter(referenced) => {
+                if referenced.get("in").and_then(Value::as_str) == Some("body") {
+                    body.push(parameter);
+                } else {
+                    body.push(referenced.clone());
+                }
+            }
+            Some(_) => other.push(parameter),
+            None if is_body_parameter(&parameter) => body.push(parameter),
+            None if parameter.get("$ref").is_some() => other.push(parameter),
+            None => other.push(convert_parameter(parameter)),
+        }
+    }
+    (body, other)
+}
+
+fn convert_parameter(parameter: Value) -> Value {
+    let Value::Object(mut parameter) = parameter else {
+        return parameter;
+    };
+    let mut schema = Map::new();
+    for field in SCHEMA_FIELDS {
+        if let Some(value) = parameter.remove(field) {
+            schema.insert(field.to_string(), value);
+        }
+    }
+    let location = parameter
+        .get("in")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    match parameter
+        .remove("collectionFormat")
+        .as_ref()
+        .and_then(Value::as_str)
+    {
+        Some("csv") if location.as_deref() == Some("query") => {
+            parameter.insert("style".to_string(), json!("form"));
+            parameter.insert("explode".to_string(), json!(false));
+        }
+        Some("multi") => {
+            parameter.insert("style".to_string(), json!("form"));
+            parameter.insert("explode".to_string(), json!(true));
+        }
+        Some("ssv") => {
+            parameter.insert("style".to_string(), json!("spaceDelimited"));
+        }
+        Some("pipes") => {
+            parameter.insert("style".to_string(), json!("pipeDelimited"));
+        }
+        _ => {}
+    }
+    parameter.insert("schema".to_string(), Value::Object(schema));
+    Value::Object(parameter)
+}
+
+/// Builds a request body from a body parameter or a set of form parameters
+fn request_body(parameters: &[Value], consumes: &[String]) -> Value {
+    let mut request_body = Map::new();
+    let body = parameters
+        .iter()
+        .find(|parameter| parameter.get("in").and_then(Value::as_str) == Some("body"));
+    let (schema, content_types) = match body {
+        Some(body) => {
+            if let Some(description) = body.get("description") {
+                request_body.insert("description".to_string(), description.clone());
+            }
+            if body.get("required").and_then(Value::as_bool) == Some(true) {
+                request_body.insert("required".to_string(), json!(true));
+            }
+            let schema = body.get("schema").cloned().unwrap_or_else(|| json!({}));
+            (schema, consumes.to_vec())
+        }
+        None => {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for parameter in parameters {
+                let Some(name) = parameter.get("name").and_then(Value::as_str) else {
+                    continue;
+                };
+                let mut schema = convert_parameter(parameter.clone())
+                    .get_mut("schema")
+                    .map(Value::take)
+                    .unwrap_or_else(|| json!({}));
+                if let Some(description) = parameter.get("description") {
+                    schema["description"] = description.clone();
+                }
+                properties.insert(name.to_string(), schema);
+                if parameter.get("required").and_then(Value::as_bool) == Some(true) {
+                    required.push(json!(name));
+                }
+            }
+            let mut schema = json!({ "type": "object", "properties": properties });
+            if !required.is_empty() {
+                schema["required"] = Value::Array(required);
+                request_body.insert("required".to_string(), json!(true));
+            }
+            let form_types: Vec<String> = consumes
+                .iter()
+                .filter(|content_type| {
+                    matches!(
+                        content_type.as_str(),
+                        "multipart/form-data" | "application/x-www-form-urlencoded"
+                    )
+                })
+                .cloned()
+                .collect();
+            let form_types = if form_types.is_empty() {
+                vec!["application/x-www-form-urlencoded".to_string()]
+            } else {
+                form_types
+            };
+            (schema, form_types)
+        }
+    };
+    let content = content_types
+        .into_iter()
+        .map(|content_type| (content_type, json!({ "schema": schema.clone() })))
+        .collect();
+    request_body.insert("content".to_string(), Value::Object(content));
+    Value::Object(request_body)
+}
+
+fn convert_response(response: Value, produces: &[String]) -> Value {
+    let Value::Object(mut response) = response else {
+        return response;
+    };
+    if response.contains_key("$ref") {
+        return Value::Object(response);
+    }
+    response.entry("description").or_insert_with(|| json!(""));
+    let examples = response
+        .remove("examples")
+        .and_then(into_object)
+        .unwrap_or_default();
+    if let Some(schema) = response.remove("schema") {
+        let content = produces
+            .iter()
+            .map(|content_type| {
+                let mut media_type = json!({ "schema": schema.clone() });
+                if let Some(example) = examples.get(content_type) {
+                    media_type["example"] = example.clone();
+                }
+                (content_type.clone(), media_type)
+            })
+            .collect();
+        response.insert("content".to_string(), Value::Object(content));
+    }
+    if let Some(Value::Object(headers)) = response.remove("headers") {
+        let headers = headers
+            .into_iter()
+            .map(|(name, header)| {
+                let Value::Object(mut header) = header else {
+                    return (name, header);
+                };
+                let mut schema = Map::new();
+                for field in SCHEMA_FIELDS {
+                    if let Some(value) = header.remove(field) {
+                        schema.insert(field.to_string(), value);
+                    }
+                }
+                header.remove("collectionFormat");
+                header.insert("schema".to_string(), Value::Object(schema));
+                (name, Value::Object(header))
+            })
+            .collect();
+        response.insert("headers".to_string(), Value::Object(headers));
+    }
+    Value::Object(response)
+}
+
+fn convert_security_scheme(scheme: Value) -> Value {
+    let Value::Object(mut scheme) = scheme else {
+        return scheme;
+    };
+    match scheme.get("type").and_then(Value::as_str) {
+        Some("basic") => {
+            scheme.insert("type".to_string(), json!("http"));
+            scheme.insert("scheme".to_string(), json!("basic"));
+        }
+        Some("oauth2") => {
+            let flow = match scheme.remove("flow").as_ref().and_then(Value::as_str) {
+                Some("implicit") => "implicit",
+                Some("password") => "password",
+                Some("application") => "clientCredentials",
+                _ => "authorizationCode",
+            };
+            let mut details = Map::new();
+            for key in ["authorizationUrl", "tokenUrl"] {
+                if let Some(url) = scheme.remove(key) {
+                    details.insert(key.to_string(), url);
+                }
+            }
+            details.insert(
+                "scopes".to_string(),
+                scheme.remove("scopes").unwrap_or_else(|| json!({})),
+            );
+            scheme.insert("flows".to_string(), json!({ flow: details }));
+        }
+        _ => {}
+    }
+    Value::Object(scheme)
+}
+
+/// Applies the schema differences between the versions everywhere a schema can appear
+fn convert_schemas(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            if let Some(nullable) = object.remove("x-nullable") {
+                object.insert("nullable".to_string(), nullable);
+            }
+            if object.get("type").and_then(Value::as_str) == Some("file") {
+                object.insert("type".to_string(), json!("string"));
+                object.insert("format".to_string(), json!("binary"));
+            }
+            if let Some(Value::String(property)) = object.get("discriminator") {
+                let discriminator = json!({ "propertyName": property });
+                object.insert("discriminator".to_string(), discriminator);
+            }
+            for (key, value) in object.iter_mut() {
+                if !matches!(key.as_str(), "example" | "examples" | "default" | "enum") {
+                    convert_schemas(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(convert_schemas),
+        _ => {}
+    }
+}
+
+/// Points references at the locations the referenced objects moved to
+fn rewrite_references(value: &mut Value, parameters: &Map<String, Value>) {
+    match value {
+        Value::Object(object) => {
+            if let Some(Value::String(reference)) = object.get_mut("$ref") {
+                if let Some(name) = reference.strip_prefix("#/definitions/") {
+                    *reference = format!("#/components/schemas/{name}");
+                } else if let Some(name) = reference.strip_prefix("#/responses/") {
+                    *reference = format!("#/components/responses/{name}");
+                } else if let Some(name) = reference.strip_prefix("#/parameters/") {
+                    *reference = match parameters.get(name) {
+                        Some(parameter) if is_body_parameter(parameter) => {
+                            format!("#/components/requestBodies/{name}")
+                        }
+                        _ => format!("#/components/parameters/{name}"),
+                    };
+                }
+            }
+            for (key, value) in object.iter_mut() {
+                if !matches!(key.as_str(), "example" | "examples" | "default" | "enum") {
+                    rewrite_references(value, parameters);
+                }
+            }
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| rewrite_references(value, parameters)),
+        _ => {}
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::utils::Diagnostic;
+
+/// Replaces each `${name}` placeholder in the text with the variable's value, the way
+/// Terraform's `templatefile` does. `$${` is an escape for a literal `${`.
+///
+/// Placeholders without a value are left as they are, and their names are added to
+/// `unresolved`.
+fn substitute(
+    text: &str,
+    variables: &HashMap<String, String>,
+    unresolved: &mut Vec<String>,
+) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(escaped) = rest.strip_prefix("$${") {
+            result.push_str("${");
+            rest = escaped;
+            continue;
+        }
+        let placeholder = rest
+            .strip_prefix("${")
+            .and_then(|inner| inner.find('}').map(|end| &inner[..end]));
+        match placeholder {
+            Some(name) => {
+                match variables.get(name.trim()) {
+                    Some(value) => result.push_str(value),
+                    None => {
+                        unresolved.push(name.trim().to_string());
+                        result.push_str(&rest[..name.len() + 3]);
+                    }
+                }
+                rest = &rest[name.len() + 3..];
+            }
+            None => {
+                result.push('$');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Substitutes the placeholders in every string within the value
+fn substitute_value(
+    value: &mut Value,
+    variables: &HashMap<String, String>,
+    unresolved: &mut Vec<String>,
+) {
+    match value {
+        Value::String(text) => *text = substitute(text, variables, unresolved),
+        Value::Array(values) => {
+            for value in values {
+                substitute_value(value, variables, unresolved);
+            }
+        }
+        Value::Object(object) => {
+            for value in object.values_mut() {
+                substitute_value(value, variables, unresolved);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Fills in a spec's template variables, if it has any, keeping track of the placeholders that
+/// had no value
+pub(crate) struct Template<'a> {
+    variables: Option<&'a HashMap<String, String>>,
+    unresolved: Vec<String>,
+}
+
+impl<'a> Template<'a> {
+    pub(crate) fn new(variables: Option<&'a HashMap<String, String>>) -> Self {
+        Self {
+            variables,
+            unresolved: Vec::new(),
+        }
+    }
+
+    pub(crate) fn substitute(&mut self, content: String) -> String {
+        match self.variables {
+            Some(variables) => substitute(&content, variables, &mut self.unresolved),
+            None => content,
+        }
+    }
+
+    /// Substitutes the placeholders in the files that were referenced with a `$ref`
+    pub(crate) fn substitute_external(
+        &mut self,
+        mut external: IndexMap<String, Value>,
+    ) -> IndexMap<String, Value> {
+        if let Some(variables) = self.variables {
+            for value in external.values_mut() {
+                substitute_value(value, variables, &mut self.unresolved);
+            }
+        }
+        external
+    }
+
+    /// A diagnostic for each placeholder that had no value, with how often it was used
+    pub(crate) fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut counts: IndexMap<&str, usize> = IndexMap::new();
+        for name in &self.unresolved {
+            *counts.entry(name).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .map(|(name, count)| Diagnostic {
+                kind: "unresolved-template-variable".to_string(),
+                name: name.to_string(),
+                message: format!(
+                    "No value for template variable ${{{name}}}, used {count} time(s)"
+                ),
+            })
+            .collect()
+    }
+}
@@ -1,10 +1,2499 @@
-use trustfall::provider::check_adapter_invariants;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    sync::Arc,
+};
 
-use crate::OpenApiAdapter;
+use openapiv3::{
+    Parameter, QueryStyle, ReferenceOr, Schema, SchemaKind, SecurityScheme, StatusCode, Type,
+};
+use trustfall::{execute_query, provider::check_adapter_invariants, FieldValue};
+
+use crate::{
+    document::Document, errors::OpenAPIAdapterErrors, LintRule, LintSeverity, OpenApiAdapter,
+    OpenApiDiffAdapter,
+};
+
+fn test_data(path: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("test_data")
+        .join(path)
+}
+
+fn run_query(adapter: OpenApiAdapter, query: &str) -> Vec<BTreeMap<Arc<str>, FieldValue>> {
+    run_query_with(adapter, query, BTreeMap::new())
+}
+
+fn run_query_with(
+    adapter: OpenApiAdapter,
+    query: &str,
+    variables: BTreeMap<Arc<str>, FieldValue>,
+) -> Vec<BTreeMap<Arc<str>, FieldValue>> {
+    execute_query(
+        OpenApiAdapter::schema(),
+        Arc::new(adapter),
+        query,
+        variables,
+    )
+    .unwrap()
+    .collect()
+}
 
 #[test]
 fn adapter_satisfies_trustfall_invariants() {
     let adapter = OpenApiAdapter::default();
     let schema = OpenApiAdapter::schema();
     check_adapter_invariants(schema, adapter);
+
+    let adapter = OpenApiDiffAdapter::new(OpenApiAdapter::default(), OpenApiAdapter::default());
+    check_adapter_invariants(OpenApiDiffAdapter::schema(), adapter);
+}
+
+#[test]
+fn reads_openapi_31_documents() {
+    let contents = std::fs::read_to_string(test_data("openapi31/pets.yaml")).unwrap();
+    let document = Document::from_str(&contents).unwrap();
+    let schemas = &document.openapi.components.as_ref().unwrap().schemas;
+    let ReferenceOr::Item(Schema {
+        schema_kind: SchemaKind::Type(Type::Object(pet)),
+        ..
+    }) = &schemas["Pet"]
+    else {
+        panic!("Pet should be an object schema");
+    };
+    let property = |name: &str| match &pet.properties[name] {
+        ReferenceOr::Item(schema) => schema.clone(),
+        ReferenceOr::Reference { .. } => panic!("{name} should be inline"),
+    };
+    assert!(property("tag").schema_data.nullable);
+    assert!(matches!(
+        property("tag").schema_kind,
+        SchemaKind::Type(Type::String(_))
+    ));
+    assert!(property("owner").schema_data.nullable);
+    assert_eq!(
+        property("name").schema_data.example,
+        Some(serde_json::json!("Rex"))
+    );
+    assert!(matches!(
+        property("kind").schema_kind,
+        SchemaKind::Any(any) if any.enumeration == [serde_json::json!("dog")]
+    ));
+
+    let rows = run_query(
+        OpenApiAdapter::new_with_path(test_data("openapi31")).unwrap(),
+        r#"
+        query {
+            Document {
+                openapiVersion @output
+                paths {
+                    path @output
+                    operations {
+                        summary @output
+                    }
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["openapiVersion"], "3.1.0".into());
+    assert_eq!(rows[0]["path"], "/pets".into());
+    assert_eq!(rows[0]["summary"], "List pets".into());
+
+    // Keywords the 3.0 model can't hold are reported, including in referenced files
+    let rows = run_query(
+        OpenApiAdapter::new_with_path(test_data("openapi31")).unwrap(),
+        r#"
+        query {
+            Diagnostics {
+                kind @output
+                name @output
+                message @output
+            }
+        }"#,
+    );
+    let dropped: Vec<_> = rows
+        .iter()
+        .filter(|row| row["kind"] == "dropped-keyword".into())
+        .map(|row| row["name"].clone())
+        .collect();
+    assert_eq!(
+        dropped,
+        vec![
+            "#/components/schemas/Pet/properties/nicknames/prefixItems".into(),
+            "#/components/schemas/Pet/properties/nicknames/examples".into(),
+            "#/components/schemas/Owner/patternProperties".into(),
+            "toy.yaml#/Toy/if".into(),
+            "toy.yaml#/Toy/then".into(),
+        ]
+    );
+    assert_eq!(
+        rows[1]["message"],
+        "Only the first of 2 examples is kept".into()
+    );
+}
+
+#[test]
+fn exposes_openapi_31_webhooks() {
+    let adapter = OpenApiAdapter::new_with_path(test_data("openapi31")).unwrap();
+    let rows = run_query(
+        adapter,
+        r#"
+        query {
+            Webhooks {
+                path @output
+                post {
+                    summary @output
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["path"], "newPet".into());
+    assert_eq!(rows[0]["summary"], "A pet was added".into());
+}
+
+#[test]
+fn converts_swagger_2_documents() {
+    let contents = std::fs::read_to_string(test_data("swagger2/users.yaml")).unwrap();
+    let document = Document::from_str(&contents).unwrap();
+    let openapi = &document.openapi;
+
+    assert_eq!(document.version, "2.0");
+    assert_eq!(openapi.servers[0].url, "https://api.example.com/v1");
+
+    let users = openapi.paths.paths["/users"].as_item().unwrap();
+    let list = users.get.as_ref().unwrap();
+    let ReferenceOr::Item(Parameter::Query {
+        parameter_data,
+        style,
+        ..
+    }) = &list.parameters[0]
+    else {
+        panic!("ids should be a query parameter");
+    };
+    assert_eq!(parameter_data.name, "ids");
+    assert_eq!(*style, QueryStyle::Form);
+    assert_eq!(parameter_data.explode, Some(true));
+    let ReferenceOr::Item(ok) = &list.responses.responses[&StatusCode::Code(200)] else {
+        panic!("200 response should be inline");
+    };
+    assert!(ok.content.contains_key("application/json"));
+
+    let create = users.post.as_ref().unwrap();
+    assert_eq!(
+        create.request_body,
+        Some(ReferenceOr::ref_("#/components/requestBodies/UserBody"))
+    );
+
+    let avatar = openapi.paths.paths["/users/{id}/avatar"].as_item().unwrap();
+    assert_eq!(avatar.parameters.len(), 1);
+    let ReferenceOr::Item(upload) = avatar.put.as_ref().unwrap().request_body.as_ref().unwrap()
+    else {
+        panic!("form parameters should become an inline request body");
+    };
+    assert!(upload.required);
+    assert!(upload.content.contains_key("multipart/form-data"));
+
+    let components = openapi.components.as_ref().unwrap();
+    assert!(components.schemas.contains_key("User"));
+    assert!(components.request_bodies.contains_key("UserBody"));
+    assert!(matches!(
+        &components.security_schemes["basic"],
+        ReferenceOr::Item(SecurityScheme::HTTP { scheme, .. }) if scheme == "basic"
+    ));
+
+    let rows = run_query(
+        OpenApiAdapter::new_with_path(test_data("swagger2")).unwrap(),
+        r#"
+        query {
+            Document {
+                openapiVersion @output
+                paths {
+                    path @output
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["openapiVersion"], "2.0".into());
+    assert_eq!(rows[0]["path"], "/users".into());
+    assert_eq!(rows[1]["path"], "/users/{id}/avatar".into());
+}
+
+#[test]
+fn merges_yaml_and_json_files() {
+    let paths = |adapter: OpenApiAdapter| {
+        let rows = run_query(
+            adapter,
+            r#"
+            query {
+                Paths {
+                    path @output
+                }
+            }"#,
+        );
+        let mut paths: Vec<_> = rows.iter().map(|row| row["path"].clone()).collect();
+        paths.sort_by_key(|path| path.as_str().unwrap().to_string());
+        paths
+    };
+    let skipped = |adapter: OpenApiAdapter| {
+        let rows = run_query(
+            adapter,
+            r#"
+            query {
+                Diagnostics {
+                    kind @output
+                    name @output
+                }
+            }"#,
+        );
+        let mut names: Vec<_> = rows
+            .iter()
+            .filter(|row| row["kind"] == "skipped-json-file".into())
+            .map(|row| row["name"].clone())
+            .collect();
+        names.sort_by_key(|name| name.as_str().unwrap().to_string());
+        names
+    };
+    let mut adapter = OpenApiAdapter::new().with_json_fragments();
+    adapter.set_path(test_data("mixed")).unwrap();
+    assert_eq!(
+        paths(adapter.clone()),
+        vec!["/customers".into(), "/orders".into()]
+    );
+    assert_eq!(skipped(adapter), Vec::<FieldValue>::new());
+
+    // Without fragments, only JSON that declares a version is merged, and other JSON such as a
+    // tsconfig.json with comments is skipped and reported
+    let adapter = OpenApiAdapter::new_with_path(test_data("mixed")).unwrap();
+    assert_eq!(paths(adapter.clone()), vec!["/orders".into()]);
+    assert_eq!(skipped(adapter), vec!["customers.json".into()]);
+    let adapter = OpenApiAdapter::new_with_path(test_data("json")).unwrap();
+    assert_eq!(paths(adapter.clone()), vec!["/invoices".into()]);
+    assert_eq!(
+        skipped(adapter),
+        vec!["package.json".into(), "tsconfig.json".into()]
+    );
+}
+
+#[test]
+fn builds_adapters_from_memory() {
+    let json = r#"{
+        "openapi": "3.0.3",
+        "info": { "title": "In memory", "version": "1.0.0" },
+        "paths": {}
+    }"#;
+    let query = r#"
+    query {
+        Info {
+            title @output
+        }
+    }"#;
+
+    let adapter: OpenApiAdapter = json.parse().unwrap();
+    assert_eq!(run_query(adapter, query)[0]["title"], "In memory".into());
+
+    let adapter = OpenApiAdapter::from_reader(json.as_bytes()).unwrap();
+    assert_eq!(run_query(adapter, query)[0]["title"], "In memory".into());
+
+    let openapi: openapiv3::OpenAPI = serde_json::from_str(json).unwrap();
+    let adapter = OpenApiAdapter::from_openapi(openapi);
+    assert_eq!(run_query(adapter, query)[0]["title"], "In memory".into());
+}
+
+#[test]
+fn resolves_local_and_external_references() {
+    let rows = run_query(
+        OpenApiAdapter::new_with_path(test_data("refs")).unwrap(),
+        r#"
+        query {
+            Paths {
+                path @output
+                refName @output
+                refPath @output
+                get @optional {
+                    summary @output
+                }
+            }
+        }"#,
+    );
+    let row = |path: &str| {
+        let row = rows
+            .iter()
+            .find(|row| row["path"] == path.into())
+            .unwrap_or_else(|| panic!("{path} should be a path"));
+        (
+            row["refName"].clone(),
+            row["refPath"].clone(),
+            row["summary"].clone(),
+        )
+    };
+    // The referenced fragments aren't merged into the document
+    assert_eq!(rows.len(), 5);
+    assert_eq!(
+        row("/users"),
+        (
+            "users.yaml".into(),
+            "paths/users.yaml#".into(),
+            "List users".into()
+        )
+    );
+    assert_eq!(
+        row("/users/{id}"),
+        (
+            "/people/{id}".into(),
+            "#/paths/~1people~1{id}".into(),
+            "Get a person".into()
+        )
+    );
+    assert_eq!(
+        row("/people/{id}"),
+        (FieldValue::Null, FieldValue::Null, "Get a person".into())
+    );
+    // Cycles are reported rather than followed forever
+    assert_eq!(
+        row("/loop"),
+        (
+            "/loop-back".into(),
+            "#/paths/~1loop-back".into(),
+            FieldValue::Null
+        )
+    );
+
+    let mut adapter = OpenApiAdapter::new();
+    adapter.files(vec![test_data("refs/openapi.yaml")]).unwrap();
+    let document = &adapter.document;
+    let manager = document
+        .resolve::<Schema>(&ReferenceOr::Reference {
+            reference: "schemas/user.yaml#/User/properties/manager".to_string(),
+        })
+        .unwrap();
+    assert_eq!(
+        manager.reference.as_deref(),
+        Some("schemas/user.yaml#/User/properties/manager")
+    );
+    assert!(matches!(
+        manager.item.schema_kind,
+        SchemaKind::Type(Type::Object(_))
+    ));
+}
+
+#[test]
+fn reports_references_that_cant_be_resolved() {
+    let adapter =
+        || OpenApiAdapter::new_with_files(vec![test_data("broken-refs/openapi.yaml")]).unwrap();
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Diagnostics {
+                kind @output
+                name @output
+                message @output
+            }
+        }"#,
+    );
+    let diagnostics: Vec<_> = rows
+        .iter()
+        .map(|row| (row["kind"].clone(), row["name"].clone()))
+        .collect();
+    assert_eq!(
+        diagnostics,
+        vec![
+            ("unresolved-reference".into(), "#/x-path-items/Users".into()),
+            // A file that doesn't exist is reported rather than failing the load
+            (
+                "unresolved-reference".into(),
+                "./paths/missing.yaml#/Invoices".into()
+            ),
+            (
+                "invalid-reference".into(),
+                "#/components/schemas/Order".into()
+            ),
+            (
+                "cyclic-reference".into(),
+                "#/components/schemas/Loop".into()
+            ),
+            (
+                "cyclic-reference".into(),
+                "#/components/schemas/Again".into()
+            ),
+        ]
+    );
+    assert_eq!(
+        rows[3]["message"],
+        "Cyclic reference: #/components/schemas/Loop -> #/components/schemas/Again -> #/components/schemas/Loop".into()
+    );
+
+    // The path whose reference is broken is still there, without operations
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Paths {
+                path @output
+                refPath @output
+                refUnresolved @output
+                operations @fold @transform(op: "count") @output(name: "operations")
+            }
+        }"#,
+    );
+    let paths: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["path"].clone(),
+                row["refUnresolved"].clone(),
+                row["operations"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            ("/users".into(), true.into(), 0u64.into()),
+            ("/invoices".into(), true.into(), 0u64.into()),
+            ("/orders".into(), false.into(), 1u64.into()),
+        ]
+    );
+    assert_eq!(rows[0]["refPath"], "#/x-path-items/Users".into());
+}
+
+#[test]
+fn exposes_effective_parameters() {
+    let rows = run_query(
+        OpenApiAdapter::new_with_files(vec![test_data("parameters/openapi.yaml")]).unwrap(),
+        r#"
+        query {
+            Path(path: "/users/{id}") {
+                get {
+                    effectiveParameters {
+                        name @output
+                        in @output
+                        required @output
+                        deprecated @output
+                        style @output
+                        explode @output
+                        refName @output
+                        schema {
+                            type @output
+                            format @output
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    let parameters: Vec<Vec<FieldValue>> = rows
+        .iter()
+        .map(|row| {
+            [
+                "name",
+                "in",
+                "required",
+                "deprecated",
+                "style",
+                "explode",
+                "refName",
+                "type",
+                "format",
+            ]
+            .iter()
+            .map(|key| row[*key].clone())
+            .collect()
+        })
+        .collect();
+    assert_eq!(
+        parameters,
+        vec![
+            vec![
+                "id".into(),
+                "path".into(),
+                true.into(),
+                false.into(),
+                "simple".into(),
+                false.into(),
+                "Id".into(),
+                "integer".into(),
+                "int64".into(),
+            ],
+            // The operation's header overrides the path's
+            vec![
+                "X-Request-Id".into(),
+                "header".into(),
+                true.into(),
+                false.into(),
+                "simple".into(),
+                false.into(),
+                FieldValue::Null,
+                "string".into(),
+                FieldValue::Null,
+            ],
+            vec![
+                "fields".into(),
+                "query".into(),
+                false.into(),
+                true.into(),
+                "form".into(),
+                true.into(),
+                FieldValue::Null,
+                "array".into(),
+                FieldValue::Null,
+            ],
+            vec![
+                "filter".into(),
+                "query".into(),
+                false.into(),
+                false.into(),
+                "form".into(),
+                true.into(),
+                FieldValue::Null,
+                "object".into(),
+                FieldValue::Null,
+            ],
+        ]
+    );
+
+    let rows = run_query(
+        OpenApiAdapter::new_with_files(vec![test_data("parameters/openapi.yaml")]).unwrap(),
+        r#"
+        query {
+            Path(path: "/users/{id}") {
+                parameters {
+                    name @output
+                    description @output
+                }
+                get {
+                    parameters @fold {
+                        operation: name @output
+                    }
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1]["description"], "Correlates requests".into());
+    assert_eq!(
+        rows[0]["operation"],
+        vec!["X-Request-Id", "fields", "filter"].into()
+    );
+}
+
+#[test]
+fn exposes_request_bodies_and_responses() {
+    let adapter =
+        || OpenApiAdapter::new_with_files(vec![test_data("bodies/openapi.yaml")]).unwrap();
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Path(path: "/orders") {
+                post {
+                    requestBody {
+                        required @output
+                        description @output
+                        refName @output
+                        content {
+                            mediaType @output
+                            schema {
+                                schema: refName @output
+                            }
+                            examples @fold {
+                                example: name @output
+                                summary @output
+                                value: valueJson @output
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["required"], true.into());
+    assert_eq!(rows[0]["description"], "The order to create".into());
+    assert_eq!(rows[0]["refName"], "Order".into());
+    assert_eq!(rows[0]["mediaType"], "application/json".into());
+    assert_eq!(rows[0]["schema"], "Order".into());
+    assert_eq!(rows[0]["example"], vec!["small", "large"].into());
+    assert_eq!(
+        rows[0]["summary"],
+        vec!["A small order", "A large order"].into()
+    );
+    assert_eq!(
+        rows[0]["value"],
+        vec![r#"{"items":1}"#, r#"{"items":100}"#].into()
+    );
+    assert_eq!(rows[1]["mediaType"], "application/xml".into());
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Path(path: "/orders") {
+                post {
+                    responses {
+                        statusCode @output
+                        description @output
+                        refPath @output
+                        headers @fold {
+                            header: name @output
+                            required @output
+                        }
+                        content @fold {
+                            mediaType @output
+                            examples @fold {
+                                value: valueJson @output
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    let responses: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["statusCode"].clone(),
+                row["description"].clone(),
+                row["refPath"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        responses,
+        vec![
+            ("201".into(), "Created".into(), FieldValue::Null),
+            (
+                "4XX".into(),
+                "Client error".into(),
+                "#/components/responses/Error".into()
+            ),
+            (
+                "default".into(),
+                "Unexpected error".into(),
+                FieldValue::Null
+            ),
+        ]
+    );
+    assert_eq!(rows[0]["header"], vec!["Location"].into());
+    assert_eq!(rows[0]["required"], vec![true].into());
+    assert_eq!(rows[0]["value"], vec![vec![r#"{"id":1}"#]].into());
+    assert_eq!(
+        rows[1]["mediaType"],
+        vec!["application/problem+json"].into()
+    );
+}
+
+#[test]
+fn walks_schemas() {
+    let adapter =
+        || OpenApiAdapter::new_with_files(vec![test_data("schemas/openapi.yaml")]).unwrap();
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Schemas {
+                name @output
+                type @output
+                required @output
+                properties @fold {
+                    property: name @output
+                    isRequired: required @output
+                }
+            }
+        }"#,
+    );
+    let names: Vec<_> = rows.iter().map(|row| row["name"].clone()).collect();
+    assert_eq!(
+        names,
+        vec!["User".into(), "Contact".into(), "Pet".into(), "Cat".into()]
+    );
+    assert_eq!(rows[0]["type"], "object".into());
+    assert_eq!(rows[0]["required"], vec!["email"].into());
+    assert_eq!(
+        rows[0]["property"],
+        vec!["email", "contact", "roles", "age"].into()
+    );
+    assert_eq!(
+        rows[0]["isRequired"],
+        vec![true, false, false, false].into()
+    );
+    assert_eq!(rows[2]["type"], FieldValue::Null);
+
+    // String properties named like an email without the email format, at any depth
+    let rows = run_query_with(
+        adapter(),
+        r#"
+        query {
+            Schemas {
+                name @filter(op: "=", value: ["$schema"])
+                subschemas @recurse(depth: 2) {
+                    properties {
+                        property: name @filter(op: "regex", value: ["$pattern"]) @output
+                        schema {
+                            type @filter(op: "=", value: ["$type"])
+                            format @filter(op: "!=", value: ["$format"])
+                        }
+                    }
+                }
+            }
+        }"#,
+        BTreeMap::from([
+            ("schema".into(), "User".into()),
+            ("pattern".into(), "(?i)email".into()),
+            ("type".into(), "string".into()),
+            ("format".into(), "email".into()),
+        ]),
+    );
+    let properties: Vec<_> = rows.iter().map(|row| row["property"].clone()).collect();
+    assert_eq!(properties, vec!["backupEmail".into()]);
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Schemas {
+                name @output
+                oneOf {
+                    variant: refName @output
+                    nullable @output
+                    allOf {
+                        properties {
+                            schema {
+                                pattern @output
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["variant"], "Cat".into());
+    assert_eq!(rows[0]["nullable"], true.into());
+    assert_eq!(rows[0]["pattern"], "^[A-Z]".into());
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Schemas {
+                name @output
+                properties {
+                    property: name @output
+                    schema {
+                        minimum @output
+                        maximum @output
+                        exclusiveMaximum @output
+                        maxLength @output
+                        items @optional {
+                            enum @output
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    let property = |schema: &str, property: &str| {
+        rows.iter()
+            .find(|row| row["name"] == schema.into() && row["property"] == property.into())
+            .unwrap_or_else(|| panic!("{schema}.{property} should be a property"))
+    };
+    let age = property("User", "age");
+    assert_eq!(age["minimum"], FieldValue::Float64(0.0));
+    assert_eq!(age["maximum"], FieldValue::Float64(150.0));
+    assert_eq!(age["exclusiveMaximum"], true.into());
+    assert_eq!(
+        property("User", "roles")["enum"],
+        vec!["admin", "viewer"].into()
+    );
+    assert_eq!(
+        property("Contact", "backupEmail")["maxLength"],
+        254_u64.into()
+    );
+    // The referenced schema's keywords are read through the reference
+    assert_eq!(property("User", "contact")["maxLength"], FieldValue::Null);
+}
+
+#[test]
+fn exposes_security_schemes_and_requirements() {
+    let adapter =
+        || OpenApiAdapter::new_with_files(vec![test_data("security/openapi.yaml")]).unwrap();
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            SecuritySchemes {
+                name @output
+                type @output
+                refName @output
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[0]["type"], "apiKey".into());
+    assert_eq!(rows[1]["name"], "bearer".into());
+    assert_eq!(rows[1]["refName"], "jwt".into());
+    assert_eq!(rows[1]["type"], "http".into());
+    assert_eq!(rows[3]["type"], "oauth2".into());
+    assert_eq!(rows[4]["type"], "openIdConnect".into());
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            SecuritySchemes {
+                ... on ApiKeySecurityScheme {
+                    in @output
+                    parameterName @output
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["in"], "header".into());
+    assert_eq!(rows[0]["parameterName"], "X-API-Key".into());
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            SecuritySchemes {
+                ... on HttpSecurityScheme {
+                    scheme @output
+                    bearerFormat @output
+                    description @output
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["scheme"], "bearer".into());
+    assert_eq!(rows[0]["bearerFormat"], "JWT".into());
+    assert_eq!(rows[0]["description"], "A signed JWT".into());
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            SecuritySchemes {
+                ... on OAuth2SecurityScheme {
+                    flows {
+                        type @output
+                        tokenUrl @output
+                        authorizationUrl @output
+                        scopes @fold {
+                            scope: name @output
+                            description @output
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["type"], "clientCredentials".into());
+    assert_eq!(rows[1]["type"], "authorizationCode".into());
+    assert_eq!(
+        rows[1]["authorizationUrl"],
+        "https://example.com/authorize".into()
+    );
+    assert_eq!(rows[1]["scope"], vec!["pets:read", "pets:write"].into());
+    assert_eq!(
+        rows[1]["description"],
+        vec!["Read pets", "Modify pets"].into()
+    );
+    assert_eq!(rows[0]["authorizationUrl"], FieldValue::Null);
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Paths {
+                path @output
+                operations {
+                    method @output
+                    public @output
+                    security @fold {
+                        schemes @fold {
+                            scheme: name @output
+                            scopes @output
+                            scheme {
+                                type @output
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    let summary: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["path"].clone(),
+                row["method"].clone(),
+                row["public"].clone(),
+                row["type"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "/health".into(),
+                "GET".into(),
+                true.into(),
+                Vec::<Vec<String>>::new().into()
+            ),
+            (
+                "/pets".into(),
+                "GET".into(),
+                true.into(),
+                vec![vec![], vec!["apiKey"]].into()
+            ),
+            (
+                "/pets".into(),
+                "POST".into(),
+                false.into(),
+                vec![vec!["oauth2"]].into()
+            ),
+            (
+                "/me".into(),
+                "GET".into(),
+                false.into(),
+                vec![vec!["http"]].into()
+            ),
+        ]
+    );
+    assert_eq!(rows[2]["scopes"], vec![vec![vec!["pets:write"]]].into());
+}
+
+#[test]
+fn resolves_servers_down_the_hierarchy() {
+    let adapter =
+        || OpenApiAdapter::new_with_files(vec![test_data("servers/openapi.yaml")]).unwrap();
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Servers {
+                url @output
+                description @output
+                expandedUrl @output
+                variables @fold {
+                    variable: name @output
+                    default @output
+                    enum @output
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 1);
+    assert_eq!(
+        rows[0]["url"],
+        "https://{environment}.example.com:{port}/v1".into()
+    );
+    assert_eq!(rows[0]["description"], "Public API".into());
+    assert_eq!(
+        rows[0]["expandedUrl"],
+        "https://api.example.com:443/v1".into()
+    );
+    assert_eq!(rows[0]["variable"], vec!["environment", "port"].into());
+    assert_eq!(rows[0]["default"], vec!["api", "443"].into());
+    assert_eq!(
+        rows[0]["enum"],
+        vec![FieldValue::from(vec!["api", "staging"]), FieldValue::Null].into()
+    );
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Paths {
+                path @output
+                servers @fold {
+                    pathServer: expandedUrl @output
+                }
+                operations {
+                    method @output
+                    servers {
+                        server: expandedUrl @output
+                    }
+                }
+            }
+        }"#,
+    );
+    let servers: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["path"].clone(),
+                row["method"].clone(),
+                row["server"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        servers,
+        vec![
+            (
+                "/pets".into(),
+                "GET".into(),
+                "https://api.example.com:443/v1".into()
+            ),
+            (
+                "/internal".into(),
+                "GET".into(),
+                "http://internal.local/v1".into()
+            ),
+            (
+                "/internal".into(),
+                "POST".into(),
+                "http://uploads.internal.local".into()
+            ),
+        ]
+    );
+    assert_eq!(
+        rows[1]["pathServer"],
+        vec!["http://internal.local/v1"].into()
+    );
+}
+
+#[test]
+fn exposes_the_full_operation_model() {
+    let adapter =
+        || OpenApiAdapter::new_with_files(vec![test_data("operations/openapi.yaml")]).unwrap();
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Path(path: "/users/{id}") {
+                head {
+                    headId: operationId @output
+                }
+                trace {
+                    traceDeprecated: deprecated @output
+                }
+                operations {
+                    method @output
+                    operationId @output
+                    deprecated @output
+                    externalDocs @optional {
+                        url @output
+                        docs: description @output
+                    }
+                }
+            }
+        }"#,
+    );
+    let operations: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["method"].clone(),
+                row["operationId"].clone(),
+                row["deprecated"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        operations,
+        vec![
+            ("GET".into(), "getUser".into(), false.into()),
+            ("HEAD".into(), "userExists".into(), false.into()),
+            ("TRACE".into(), "traceUser".into(), true.into()),
+        ]
+    );
+    assert_eq!(rows[0]["headId"], "userExists".into());
+    assert_eq!(rows[0]["traceDeprecated"], true.into());
+    assert_eq!(rows[0]["url"], "https://docs.example.com/users".into());
+    assert_eq!(rows[0]["docs"], "User guide".into());
+    assert_eq!(rows[1]["url"], FieldValue::Null);
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Path(path: "/users") {
+                post {
+                    callbacks {
+                        name @output
+                        expression @output
+                        path {
+                            callbackPath: path @output
+                            operations {
+                                callbackOperation: operationId @output
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["name"], "userCreated".into());
+    assert_eq!(rows[0]["expression"], "{$request.body#/callbackUrl}".into());
+    assert_eq!(rows[0]["callbackPath"], rows[0]["expression"]);
+    assert_eq!(rows[0]["callbackOperation"], "userCreatedCallback".into());
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Path(path: "/users") {
+                post {
+                    responses {
+                        links {
+                            name @output
+                            operationId @output
+                            operationRef @output
+                            parametersJson @output
+                            refName @output
+                            operation {
+                                method @output
+                                target: operationId @output
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["name"], "GetUser".into());
+    assert_eq!(rows[0]["operationId"], "getUser".into());
+    assert_eq!(
+        rows[0]["parametersJson"],
+        r#"{"id":"$response.body#/id"}"#.into()
+    );
+    assert_eq!(rows[0]["target"], "getUser".into());
+    assert_eq!(rows[1]["refName"], "UserExists".into());
+    assert_eq!(rows[1]["operationRef"], "#/paths/~1users~1{id}/head".into());
+    assert_eq!(rows[1]["method"], "HEAD".into());
+    assert_eq!(rows[1]["target"], "userExists".into());
+}
+
+#[test]
+fn exposes_vendor_extensions() {
+    let adapter =
+        || OpenApiAdapter::new_with_files(vec![test_data("extensions/openapi.yaml")]).unwrap();
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Document {
+                extensions {
+                    document: name @output
+                }
+                info {
+                    extensions {
+                        info: valueJson @output
+                    }
+                }
+                paths {
+                    extensions {
+                        path: name @output
+                        value {
+                            type @output
+                            field(name: "tier") {
+                                tier: string @output
+                            }
+                            entries @fold {
+                                entry: key @output
+                            }
+                        }
+                    }
+                    get {
+                        extensions @fold {
+                            operation: name @output
+                            operationValue: valueJson @output
+                        }
+                        parameters {
+                            extensions {
+                                parameter: name @output
+                                value {
+                                    internal: boolean @output
+                                }
+                            }
+                            schema {
+                                extensions {
+                                    schema: name @output
+                                }
+                            }
+                        }
+                        responses {
+                            extensions {
+                                response: name @output
+                                value {
+                                    seconds: number @output
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(row["document"], "x-visibility".into());
+    assert_eq!(row["info"], r#""platform-team""#.into());
+    assert_eq!(row["path"], "x-rate-limit".into());
+    assert_eq!(row["type"], "object".into());
+    assert_eq!(row["tier"], "gold".into());
+    assert_eq!(row["entry"], vec!["tier", "limits"].into());
+    assert_eq!(row["operation"], vec!["x-owner", "x-beta"].into());
+    assert_eq!(
+        row["operationValue"],
+        vec![r#""orders-team""#, "true"].into()
+    );
+    assert_eq!(row["parameter"], "x-internal".into());
+    assert_eq!(row["internal"], false.into());
+    assert_eq!(row["schema"], "x-unit".into());
+    assert_eq!(row["response"], "x-cache-seconds".into());
+    assert_eq!(row["seconds"], FieldValue::Float64(60.0));
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Path(path: "/orders") {
+                extensions {
+                    value {
+                        field(name: "limits") {
+                            entries {
+                                index: key @output
+                                limit: number @output
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    let limits: Vec<_> = rows
+        .iter()
+        .map(|row| (row["index"].clone(), row["limit"].clone()))
+        .collect();
+    assert_eq!(
+        limits,
+        vec![
+            ("0".into(), FieldValue::Float64(100.0)),
+            ("1".into(), FieldValue::Float64(1000.0)),
+        ]
+    );
+}
+
+#[test]
+fn models_aws_integrations() {
+    let adapter = || OpenApiAdapter::new_with_files(vec![test_data("aws/openapi.yaml")]).unwrap();
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Paths {
+                path @output
+                operations {
+                    method @output
+                    xAmazonApigatewayIntegration {
+                        type @output
+                        trigger @output
+                        arn @output
+                        connectionType @output
+                        uriArn @optional {
+                            region @output
+                            service @output
+                            action @output
+                            functionName @output
+                            stateMachineArn @output
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    let integrations: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["path"].clone(),
+                row["method"].clone(),
+                row["trigger"].clone(),
+                row["service"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        integrations,
+        vec![
+            (
+                "/users".into(),
+                "GET".into(),
+                "Lambda".into(),
+                "lambda".into()
+            ),
+            (
+                "/users".into(),
+                "POST".into(),
+                "Lambda".into(),
+                "lambda".into()
+            ),
+            (
+                "/orders".into(),
+                "POST".into(),
+                "Step Function".into(),
+                "states".into()
+            ),
+            (
+                "/proxy".into(),
+                "GET".into(),
+                "HTTP".into(),
+                FieldValue::Null
+            ),
+            (
+                "/health".into(),
+                "GET".into(),
+                "Mock".into(),
+                FieldValue::Null
+            ),
+        ]
+    );
+    assert_eq!(rows[0]["region"], "${AWS::Region}".into());
+    assert_eq!(rows[0]["arn"], "list_users_arn".into());
+    assert_eq!(rows[0]["functionName"], "list_users_arn".into());
+    assert_eq!(
+        rows[1]["arn"],
+        "arn:aws:lambda:us-east-1:123456789012:function:create-user:live".into()
+    );
+    assert_eq!(rows[1]["functionName"], "create-user".into());
+    assert_eq!(rows[2]["action"], "StartExecution".into());
+    assert_eq!(
+        rows[2]["stateMachineArn"],
+        "arn:aws:states:eu-west-1:123456789012:stateMachine:orders".into()
+    );
+    assert_eq!(rows[3]["connectionType"], "VPC_LINK".into());
+    assert_eq!(rows[4]["arn"], FieldValue::Null);
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Path(path: "/users") {
+                post {
+                    xAmazonApigatewayIntegration {
+                        requestParameters {
+                            destination: key @output
+                            source: value @output
+                        }
+                        responses @fold {
+                            selectionPattern @output
+                            statusCode @output
+                            responseTemplates @fold {
+                                contentType: key @output
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 1);
+    assert_eq!(
+        rows[0]["destination"],
+        "integration.request.header.X-Request-Id".into()
+    );
+    assert_eq!(rows[0]["source"], "context.requestId".into());
+    assert_eq!(
+        rows[0]["selectionPattern"],
+        vec!["default", ".*Invalid.*"].into()
+    );
+    assert_eq!(rows[0]["statusCode"], vec!["201", "400"].into());
+    assert_eq!(
+        rows[0]["contentType"],
+        vec![Vec::<String>::new(), vec!["application/json".to_string()]].into()
+    );
+}
+
+#[test]
+fn models_aws_document_extensions() {
+    let adapter = || OpenApiAdapter::new_with_files(vec![test_data("aws/gateway.yaml")]).unwrap();
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Document {
+                xAmazonApigatewayApiKeySource @output
+                xAmazonApigatewayBinaryMediaTypes @output
+                xAmazonApigatewayRequestValidators @fold {
+                    validator: name @output
+                    validateRequestBody @output
+                }
+                xAmazonApigatewayRequestValidator {
+                    defaultValidator: name @output
+                }
+                xAmazonApigatewayCors {
+                    allowOrigins @output
+                    allowMethods @output
+                    allowCredentials @output
+                    maxAge @output
+                }
+                xAmazonApigatewayGatewayResponses @fold {
+                    responseType @output
+                    statusCode @output
+                    responseParameters @fold {
+                        parameter: key @output
+                    }
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(row["xAmazonApigatewayApiKeySource"], "HEADER".into());
+    assert_eq!(
+        row["xAmazonApigatewayBinaryMediaTypes"],
+        vec!["image/png", "application/octet-stream"].into()
+    );
+    assert_eq!(row["validator"], vec!["all", "params-only"].into());
+    assert_eq!(row["validateRequestBody"], vec![true, false].into());
+    assert_eq!(row["defaultValidator"], "params-only".into());
+    assert_eq!(row["allowOrigins"], vec!["https://example.com"].into());
+    assert_eq!(row["allowMethods"], vec!["GET", "POST"].into());
+    assert_eq!(row["allowCredentials"], true.into());
+    assert_eq!(row["maxAge"], 3600.into());
+    assert_eq!(
+        row["responseType"],
+        vec!["DEFAULT_4XX", "UNAUTHORIZED"].into()
+    );
+    assert_eq!(
+        row["statusCode"],
+        vec![FieldValue::Null, "401".into()].into()
+    );
+    assert_eq!(
+        row["parameter"],
+        vec![
+            vec!["gatewayresponse.header.Access-Control-Allow-Origin".to_string()],
+            vec![]
+        ]
+        .into()
+    );
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Path(path: "/items") {
+                operations {
+                    method @output
+                    xAmazonApigatewayRequestValidator {
+                        validator: name @output
+                    }
+                    security {
+                        schemes {
+                            scheme {
+                                xAmazonApigatewayAuthorizer {
+                                    type @output
+                                    resultTtlInSeconds @output
+                                    providerArns @output
+                                    uriArn @optional {
+                                        functionName @output
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    let operations: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["method"].clone(),
+                row["validator"].clone(),
+                row["type"].clone(),
+                row["resultTtlInSeconds"].clone(),
+                row["functionName"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        operations,
+        vec![
+            (
+                "GET".into(),
+                "params-only".into(),
+                "token".into(),
+                0.into(),
+                "authorize".into()
+            ),
+            (
+                "POST".into(),
+                "all".into(),
+                "cognito_user_pools".into(),
+                FieldValue::Null,
+                FieldValue::Null
+            ),
+        ]
+    );
+    assert_eq!(
+        rows[1]["providerArns"],
+        vec!["arn:aws:cognito-idp:us-east-1:123456789012:userpool/us-east-1_abc"].into()
+    );
+
+    // Extensions that can't be read are left out and reported instead
+    let adapter = || OpenApiAdapter::new_with_files(vec![test_data("aws/malformed.yaml")]).unwrap();
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Path(path: "/users") {
+                get {
+                    xAmazonApigatewayIntegration @optional {
+                        trigger @output
+                    }
+                    xAmazonApigatewayRequestValidator @optional {
+                        validateRequestBody @output
+                    }
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["trigger"], FieldValue::Null);
+    assert_eq!(rows[0]["validateRequestBody"], FieldValue::Null);
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Diagnostics {
+                kind @output
+                name @output
+                message @output
+            }
+        }"#,
+    );
+    let diagnostics: Vec<_> = rows
+        .iter()
+        .map(|row| (row["kind"].clone(), row["name"].clone()))
+        .collect();
+    assert_eq!(
+        diagnostics,
+        vec![
+            (
+                "invalid-extension".into(),
+                "#/x-amazon-apigateway-request-validators".into()
+            ),
+            (
+                "invalid-extension".into(),
+                "#/paths/~1users/get/x-amazon-apigateway-integration".into()
+            ),
+        ]
+    );
+    assert!(rows[1]["message"].as_str().unwrap().starts_with(
+        "Failed to deserialize x-amazon-apigateway-integration: missing field `type`"
+    ));
+}
+
+#[test]
+fn substitutes_template_variables() {
+    let variables: HashMap<String, String> = [
+        ("api_name", "Users"),
+        ("region", "eu-west-1"),
+        (
+            "list_users_arn",
+            "arn:aws:lambda:eu-west-1:123456789012:function:list-users",
+        ),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()))
+    .collect();
+    let adapter = || {
+        let mut adapter = OpenApiAdapter::new().with_template_variables(variables.clone());
+        adapter
+            .files(vec![test_data("templates/openapi.yaml")])
+            .unwrap();
+        adapter
+    };
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Info {
+                title @output
+                description @output
+            }
+        }"#,
+    );
+    assert_eq!(rows[0]["title"], "Users".into());
+    assert_eq!(
+        rows[0]["description"],
+        "Deployed with Terraform, which writes ${var} for a literal placeholder".into()
+    );
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Path(path: "/users") {
+                operations {
+                    method @output
+                    xAmazonApigatewayIntegration {
+                        uriArn {
+                            region @output
+                            functionName @output
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    let integrations: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["method"].clone(),
+                row["region"].clone(),
+                row["functionName"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        integrations,
+        vec![
+            ("GET".into(), "eu-west-1".into(), "list-users".into()),
+            ("POST".into(), "eu-west-1".into(), "create_user_arn".into()),
+        ]
+    );
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Diagnostics {
+                kind @output
+                name @output
+                message @output
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["kind"], "unresolved-template-variable".into());
+    assert_eq!(rows[0]["name"], "create_user_arn".into());
+    assert_eq!(
+        rows[0]["message"],
+        "No value for template variable ${create_user_arn}, used 1 time(s)".into()
+    );
+
+    let rows = run_query(
+        OpenApiAdapter::new_with_files(vec![test_data("templates/openapi.yaml")]).unwrap(),
+        r#"
+        query {
+            Diagnostics {
+                name @output
+            }
+        }"#,
+    );
+    assert!(rows.is_empty());
+}
+
+#[test]
+fn loads_specs_as_separate_documents() {
+    let adapter = || {
+        let mut adapter = OpenApiAdapter::new().with_separate_documents();
+        adapter.set_path(test_data("services")).unwrap();
+        adapter
+    };
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Documents {
+                sourceFile @output
+                info {
+                    title @output
+                }
+                tags @fold {
+                    tag: name @output
+                }
+                paths @fold {
+                    path @output
+                }
+            }
+        }"#,
+    );
+    let documents: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["sourceFile"].clone(),
+                row["title"].clone(),
+                row["tag"].clone(),
+                row["path"].clone(),
+            )
+        })
+        .collect();
+    // The shared fragment isn't a document of its own
+    assert_eq!(
+        documents,
+        vec![
+            (
+                "orders.json".into(),
+                "Orders".into(),
+                vec!["orders"].into(),
+                vec!["/orders", "/health"].into()
+            ),
+            (
+                "users.yaml".into(),
+                "Users".into(),
+                vec!["users"].into(),
+                vec!["/users", "/health"].into()
+            ),
+        ]
+    );
+
+    // Queries can span the documents, e.g. to find operationIds used by more than one service
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Documents {
+                sourceFile @output
+                paths {
+                    get {
+                        operationId @output
+                        responses {
+                            content @optional {
+                                schema {
+                                    type @output
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    let mut services: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for row in &rows {
+        let FieldValue::String(operation_id) = &row["operationId"] else {
+            panic!("every operation should have an operationId");
+        };
+        let FieldValue::String(source_file) = &row["sourceFile"] else {
+            panic!("every document should have a source file");
+        };
+        services
+            .entry(operation_id.to_string())
+            .or_default()
+            .push(source_file.to_string());
+    }
+    assert_eq!(services["list"], vec!["orders.json", "users.yaml"]);
+    assert_eq!(services["health"], vec!["orders.json", "users.yaml"]);
+    // Both documents resolve the shared fragment
+    assert_eq!(
+        rows.iter()
+            .filter(|row| row["type"] == "object".into())
+            .count(),
+        2
+    );
+
+    // The other entrypoints query the first document
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Info {
+                title @output
+            }
+        }"#,
+    );
+    assert_eq!(rows[0]["title"], "Orders".into());
+
+    let rows = run_query(
+        OpenApiAdapter::new_with_files(vec![test_data("operations/openapi.yaml")]).unwrap(),
+        r#"
+        query {
+            Documents {
+                sourceFile @output
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["sourceFile"], FieldValue::Null);
+}
+
+#[test]
+fn tracks_where_merged_parts_came_from() {
+    let adapter = || {
+        OpenApiAdapter::new_with_files(vec![
+            test_data("split/openapi.yaml"),
+            test_data("split/orders/openapi.yaml"),
+        ])
+        .unwrap()
+    };
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Paths {
+                path @output
+                pathSource: sourceFile @output
+                operations {
+                    method @output
+                    sourceFile @output
+                }
+            }
+        }"#,
+    );
+    let operations: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["path"].clone(),
+                row["pathSource"].clone(),
+                row["method"].clone(),
+                row["sourceFile"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        operations,
+        vec![
+            (
+                "/users".into(),
+                "orders/openapi.yaml".into(),
+                "GET".into(),
+                "openapi.yaml".into()
+            ),
+            (
+                "/users".into(),
+                "orders/openapi.yaml".into(),
+                "POST".into(),
+                "orders/openapi.yaml".into()
+            ),
+            (
+                "/orders".into(),
+                "orders/openapi.yaml".into(),
+                "GET".into(),
+                "orders/openapi.yaml".into()
+            ),
+        ]
+    );
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Document {
+                schemas @fold {
+                    schema: name @output
+                    schemaSource: sourceFile @output
+                }
+                securitySchemes @fold {
+                    scheme: name @output
+                    schemeSource: sourceFile @output
+                }
+                tags @fold {
+                    tag: name @output
+                    tagSource: sourceFile @output
+                }
+            }
+        }"#,
+    );
+    let row = &rows[0];
+    assert_eq!(row["schema"], vec!["User", "Address", "Order"].into());
+    assert_eq!(
+        row["schemaSource"],
+        vec![
+            "orders/openapi.yaml",
+            "schemas/address.yaml",
+            "orders/openapi.yaml"
+        ]
+        .into()
+    );
+    assert_eq!(row["scheme"], vec!["apiKey"].into());
+    assert_eq!(row["schemeSource"], vec!["openapi.yaml"].into());
+    // Lists are replaced rather than merged, so only the last file's tags are left
+    assert_eq!(row["tag"], vec!["orders"].into());
+    assert_eq!(row["tagSource"], vec!["orders/openapi.yaml"].into());
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            MergeConflicts {
+                key @output
+                sourceFiles @output
+                valuesJson @output
+                sourceFile @output
+            }
+        }"#,
+    );
+    let conflicts: Vec<_> = rows
+        .iter()
+        .map(|row| (row["key"].clone(), row["valuesJson"].clone()))
+        .collect();
+    assert_eq!(
+        conflicts,
+        vec![
+            (
+                "/info/version".into(),
+                vec!["\"1.0.0\"", "\"1.1.0\""].into()
+            ),
+            (
+                "/tags".into(),
+                vec![r#"[{"name":"users"}]"#, r#"[{"name":"orders"}]"#].into()
+            ),
+            (
+                "/components/schemas/User/description".into(),
+                vec!["\"A customer\"", "\"A customer or an admin\""].into()
+            ),
+        ]
+    );
+    assert_eq!(
+        rows[0]["sourceFiles"],
+        vec!["openapi.yaml", "orders/openapi.yaml"].into()
+    );
+    assert_eq!(rows[0]["sourceFile"], "orders/openapi.yaml".into());
+}
+
+#[test]
+fn locates_vertices_in_their_source_files() {
+    let adapter = || OpenApiAdapter::new_with_path(test_data("locations")).unwrap();
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Path(path: "/users/{id}") {
+                sourceFile @output
+                line @output
+                column @output
+                get {
+                    operationLine: line @output
+                    operationColumn: column @output
+                    effectiveParameters {
+                        name @output
+                        parameterLine: line @output
+                        parameterColumn: column @output
+                        schema {
+                            schemaLine: line @output
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    let parameters: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["name"].clone(),
+                row["parameterLine"].clone(),
+                row["parameterColumn"].clone(),
+                row["schemaLine"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(rows[0]["sourceFile"], "openapi.yaml".into());
+    assert_eq!(rows[0]["line"], 9.into());
+    assert_eq!(rows[0]["column"], 3.into());
+    assert_eq!(rows[0]["operationLine"], 12.into());
+    assert_eq!(rows[0]["operationColumn"], 5.into());
+    // A referenced parameter is where the reference points
+    assert_eq!(
+        parameters,
+        vec![
+            ("id".into(), 27.into(), 5.into(), 31.into()),
+            ("expand".into(), 14.into(), 11.into(), 16.into()),
+        ]
+    );
+
+    let rows = run_query(
+        adapter(),
+        r#"
+        query {
+            Path(path: "/users/{id}") {
+                get {
+                    responses {
+                        statusCode @output
+                        line @output
+                        content {
+                            schema {
+                                sourceFile @output
+                                schemaLine: line @output
+                                schemaColumn: column @output
+                                properties {
+                                    schema {
+                                        propertyLine: line @output
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    let row = &rows[0];
+    assert_eq!(row["line"], 19.into());
+    assert_eq!(row["sourceFile"], "schemas.json".into());
+    assert_eq!(row["schemaLine"], 2.into());
+    assert_eq!(row["schemaColumn"], 3.into());
+    assert_eq!(row["propertyLine"], 5.into());
+
+    // Documents read from memory are located too, though they have no source file
+    let contents = std::fs::read_to_string(test_data("locations/openapi.yaml")).unwrap();
+    let rows = run_query(
+        contents.parse().unwrap(),
+        r#"
+        query {
+            Tags {
+                name @output
+                sourceFile @output
+                line @output
+                column @output
+            }
+        }"#,
+    );
+    assert_eq!(rows[0]["name"], "users".into());
+    assert_eq!(rows[0]["sourceFile"], FieldValue::Null);
+    assert_eq!(rows[0]["line"], 6.into());
+    assert_eq!(rows[0]["column"], 5.into());
+}
+
+#[test]
+fn classifies_changes_between_specs() {
+    let old = OpenApiAdapter::new_with_files(vec![test_data("diff/old.yaml")]).unwrap();
+    let new = OpenApiAdapter::new_with_files(vec![test_data("diff/new.yaml")]).unwrap();
+    let rows: Vec<_> = execute_query(
+        OpenApiDiffAdapter::schema(),
+        Arc::new(OpenApiDiffAdapter::new(old, new)),
+        r#"
+        query {
+            Changes {
+                kind @output
+                severity @output
+                location @output
+                message @output
+                old @optional {
+                    oldLine: line @output
+                }
+                new @optional {
+                    newLine: line @output
+                }
+            }
+        }"#,
+        BTreeMap::<Arc<str>, FieldValue>::new(),
+    )
+    .unwrap()
+    .collect();
+    let changes: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["kind"].clone(),
+                row["severity"].clone(),
+                row["location"].clone(),
+            )
+        })
+        .collect();
+    let change = |kind: &str, severity: &str, location: &str| {
+        (kind.into(), severity.into(), location.into())
+    };
+    // The same schema change breaks requests and responses in opposite directions
+    assert_eq!(
+        changes,
+        vec![
+            change(
+                "requestSchemaNarrowed",
+                "breaking",
+                "GET /users query parameter limit"
+            ),
+            change(
+                "requiredParameterAdded",
+                "breaking",
+                "GET /users query parameter tenant"
+            ),
+            change(
+                "propertyRemoved",
+                "breaking",
+                "GET /users response 200 application/json [].nickname"
+            ),
+            change(
+                "enumValueRemoved",
+                "non-breaking",
+                "GET /users response 200 application/json [].status"
+            ),
+            change(
+                "enumValueAdded",
+                "breaking",
+                "GET /users response 200 application/json [].status"
+            ),
+            change(
+                "propertyRemoved",
+                "non-breaking",
+                "POST /users request body application/json nickname"
+            ),
+            change(
+                "enumValueRemoved",
+                "breaking",
+                "POST /users request body application/json status"
+            ),
+            change(
+                "enumValueAdded",
+                "non-breaking",
+                "POST /users request body application/json status"
+            ),
+            change("responseAdded", "non-breaking", "POST /users response 409"),
+            change("pathRemoved", "breaking", "/users/{id}/avatar"),
+            change("pathAdded", "non-breaking", "/users/{id}"),
+        ]
+    );
+    assert_eq!(rows[0]["message"], "maximum changed from 100 to 50".into());
+    assert_eq!(rows[0]["oldLine"], 12.into());
+    assert_eq!(rows[0]["newLine"], 12.into());
+    assert_eq!(
+        rows[3]["message"],
+        "enum value \"archived\" was removed".into()
+    );
+    // Removed items are only in the old spec
+    assert_eq!(rows[9]["oldLine"], 35.into());
+    assert_eq!(rows[9]["newLine"], FieldValue::Null);
+
+    // Only the diff adapter has changes to list
+    let query = "query { Changes { kind @output } }";
+    assert!(execute_query(
+        OpenApiAdapter::schema(),
+        Arc::new(OpenApiAdapter::default()),
+        query,
+        BTreeMap::<Arc<str>, FieldValue>::new(),
+    )
+    .is_err());
+}
+
+#[test]
+fn lints_specs_with_bundled_and_custom_rules() {
+    let adapter = OpenApiAdapter::new_with_files(vec![test_data("lint/openapi.yaml")]).unwrap();
+    let mut rules = LintRule::bundled();
+    rules.extend(LintRule::from_path(test_data("lint/rules")).unwrap());
+    let violations = adapter.lint_with(&rules).unwrap();
+    let summary: Vec<_> = violations
+        .iter()
+        .map(|violation| {
+            (
+                violation.rule.as_str(),
+                violation.severity,
+                violation.message.as_str(),
+                violation.position.map(|position| position.line),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "operation-operationId",
+                LintSeverity::Warning,
+                "GET /orderItems has no operationId",
+                Some(33)
+            ),
+            (
+                "operation-operationId-unique",
+                LintSeverity::Error,
+                "GET /orders has the operationId listOrders, which another operation has too",
+                Some(9)
+            ),
+            (
+                "operation-operationId-unique",
+                LintSeverity::Error,
+                "POST /orders has the operationId listOrders, which another operation has too",
+                Some(19)
+            ),
+            (
+                "operation-tag-defined",
+                LintSeverity::Warning,
+                "POST /orders uses tags that aren't declared: billing",
+                Some(19)
+            ),
+            (
+                "operation-4xx-response",
+                LintSeverity::Warning,
+                "POST /orders has no 4xx response",
+                Some(19)
+            ),
+            (
+                "paths-kebab-case",
+                LintSeverity::Warning,
+                "/orderItems isn't kebab-case",
+                Some(32)
+            ),
+            (
+                "no-unused-components",
+                LintSeverity::Warning,
+                "Unused in components.schemas isn't used",
+                Some(43)
+            ),
+            (
+                "operation-summary",
+                LintSeverity::Info,
+                "GET /orderItems has no summary",
+                Some(33)
+            ),
+        ]
+    );
+    assert_eq!(violations[0].source_file.as_deref(), Some("openapi.yaml"));
+    assert_eq!(violations[0].position.unwrap().column, 5);
+    // The bundled rules are what lint runs on its own
+    assert_eq!(adapter.lint().unwrap().len(), violations.len() - 1);
+
+    let rule: LintRule = r#"
+        id: broken
+        description: Queries a type that doesn't exist
+        severity: error
+        message: broken
+        query: "query { Nothing { name @output } }"
+    "#
+    .parse()
+    .unwrap();
+    assert!(matches!(
+        adapter.lint_with(&[rule]),
+        Err(OpenAPIAdapterErrors::InvalidLintRule(id, _)) if id == "broken"
+    ));
+
+    // Operations without an operationId don't share one
+    let adapter =
+        OpenApiAdapter::new_with_files(vec![test_data("lint/no-operation-ids.yaml")]).unwrap();
+    let rules: Vec<_> = adapter
+        .lint()
+        .unwrap()
+        .into_iter()
+        .map(|violation| violation.rule)
+        .collect();
+    assert_eq!(
+        rules,
+        vec!["operation-operationId", "operation-operationId"]
+    );
+
+    // Every kind of component is checked, and a reference back into the spec from a file it
+    // references counts as a use
+    let adapter =
+        OpenApiAdapter::new_with_files(vec![test_data("lint/components/openapi.yaml")]).unwrap();
+    let unused: Vec<_> = adapter
+        .lint()
+        .unwrap()
+        .into_iter()
+        .filter(|violation| violation.rule == "no-unused-components")
+        .map(|violation| violation.message)
+        .collect();
+    assert_eq!(
+        unused,
+        vec![
+            "Unused in components.schemas isn't used",
+            "Offset in components.parameters isn't used",
+            "Gone in components.responses isn't used",
+            "Order in components.requestBodies isn't used",
+            "RateLimit in components.headers isn't used",
+            "Order in components.examples isn't used",
+            "oauth in components.securitySchemes isn't used",
+        ]
+    );
+    let rows = run_query(
+        adapter,
+        r#"
+        query {
+            Schemas {
+                name @output
+                referenceCount @output
+            }
+        }"#,
+    );
+    assert_eq!(rows[0]["name"], "Money".into());
+    assert_eq!(rows[0]["referenceCount"], 1i64.into());
+}
+
+#[test]
+fn validates_examples_against_their_schemas() {
+    let adapter = OpenApiAdapter::new_with_files(vec![test_data("examples/openapi.yaml")]).unwrap();
+    let rows = run_query(
+        adapter.clone(),
+        r#"
+        query {
+            Paths {
+                operations {
+                    parameters {
+                        parameter: name @output
+                        examples {
+                            name @output
+                            isValid @output
+                            validationErrors @fold {
+                                keyword @output
+                                message @output
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    let parameters: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["parameter"].clone(),
+                row["name"].clone(),
+                row["isValid"].clone(),
+                row["keyword"].clone(),
+                row["message"].clone(),
+            )
+        })
+        .collect();
+    let none: Vec<&str> = Vec::new();
+    assert_eq!(
+        parameters,
+        vec![
+            (
+                "limit".into(),
+                "small".into(),
+                true.into(),
+                none.clone().into(),
+                none.clone().into()
+            ),
+            (
+                "limit".into(),
+                "huge".into(),
+                false.into(),
+                vec!["maximum"].into(),
+                vec!["1000 is greater than 100"].into()
+            ),
+            (
+                "status".into(),
+                "example".into(),
+                false.into(),
+                vec!["enum"].into(),
+                vec![r#""lost" isn't one of the allowed values"#].into()
+            ),
+        ]
+    );
+
+    // Referenced examples are resolved, and ones without a value have nothing to check
+    let rows = run_query(
+        adapter.clone(),
+        r#"
+        query {
+            Paths {
+                operations {
+                    responses {
+                        content {
+                            examples {
+                                name @output
+                                isValid @output
+                                validationErrors @fold {
+                                    instancePath @output
+                                    keyword @output
+                                    message @output
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }"#,
+    );
+    let media_type: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["name"].clone(),
+                row["isValid"].clone(),
+                row["instancePath"].clone(),
+                row["keyword"].clone(),
+                row["message"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        media_type,
+        vec![
+            (
+                "valid".into(),
+                true.into(),
+                none.clone().into(),
+                none.clone().into(),
+                none.clone().into()
+            ),
+            (
+                "broken".into(),
+                false.into(),
+                vec!["/0/quantity", "/0", "/1"].into(),
+                vec!["minimum", "additionalProperties", "required"].into(),
+                vec![
+                    "0 is less than 1",
+                    "property 'note' isn't allowed",
+                    "missing required property 'id'"
+                ]
+                .into()
+            ),
+            (
+                "linked".into(),
+                true.into(),
+                none.clone().into(),
+                none.clone().into(),
+                none.clone().into()
+            ),
+        ]
+    );
+
+    let rows = run_query(
+        adapter,
+        r#"
+        query {
+            Schemas {
+                schema: name @output
+                examples {
+                    isValid @output
+                    validationErrors {
+                        instancePath @output
+                        keyword @output
+                        message @output
+                    }
+                }
+            }
+        }"#,
+    );
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[0]["schema"], "Order".into());
+    assert_eq!(rows[0]["isValid"], false.into());
+    assert_eq!(rows[0]["instancePath"], "/id".into());
+    assert_eq!(rows[0]["keyword"], "pattern".into());
+    assert_eq!(
+        rows[0]["message"],
+        "'order-1' doesn't match ^ord_[0-9]+$".into()
+    );
+
+    // A schema that refers to itself is checked once at each path rather than forever. One
+    // that mustn't match itself can't be matched by anything
+    let category: Vec<_> = rows[1..]
+        .iter()
+        .map(|row| {
+            (
+                row["schema"].clone(),
+                row["instancePath"].clone(),
+                row["keyword"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        category,
+        vec![
+            ("Category".into(), "/parent/name".into(), "pattern".into()),
+            ("Category".into(), "/parent".into(), "not".into()),
+            ("Category".into(), "".into(), "not".into()),
+        ]
+    );
 }
@@ -1,67 +1,234 @@
-use std::{ffi::OsStr, io::Read, path::PathBuf};
+use std::{
+    ffi::OsStr,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
-use openapiv3::{Operation, PathItem};
-use serde::{Deserialize, Serialize};
+use indexmap::IndexMap;
+use openapiv3::{
+    Example, MediaType, OAuth2Flows, Operation, Parameter, ParameterSchemaOrContent, PathItem,
+    ReferenceOr, RequestBody, Response, Schema, SecurityRequirement, Server,
+};
+use serde_json::Value;
 use yaml_hash::YamlHash;
 
-use crate::errors::OpenAPIAdapterErrors;
+use crate::{
+    aws::{self, AmazonApigatewayIntegration, RequestValidator},
+    document::Document,
+    errors::OpenAPIAdapterErrors,
+    provenance::Provenance,
+    refs::{self, Resolved},
+};
 
-pub(crate) fn merge(files: Vec<PathBuf>) -> Result<String, OpenAPIAdapterErrors> {
+/// Files that were referenced with a `$ref`, keyed by their path relative to the spec
+pub(crate) type ExternalFiles = IndexMap<String, Value>;
+
+/// Merges the files into one document, loading the files they reference with `$ref`s.
+///
+/// Files that are referenced by another file are fragments of the spec rather than parts of
+/// the document, so they're left out of the merge unless they declare an OpenAPI version.
+/// Which file each part of the merged document came from is tracked as it's merged.
+pub(crate) fn merge(
+    files: Vec<PathBuf>,
+    root: &Path,
+) -> Result<(String, ExternalFiles, Provenance), OpenAPIAdapterErrors> {
+    let (mut parts, external) = read_files(files, root)?;
+    parts.retain(|(key, value)| !external.contains_key(key) || is_document(value));
     let mut hash = YamlHash::new();
+    for (_, value) in &parts {
+        hash = hash
+            .merge_str(&to_yaml(value)?)
+            .map_err(|e| OpenAPIAdapterErrors::FailedToMerge(e.to_string()))?;
+    }
+
+    Ok((hash.to_string(), external, Provenance::new(&parts)))
+}
+
+/// Reads each file that declares an OpenAPI or Swagger version as a document of its own,
+/// loading the files they reference with `$ref`s.
+///
+/// Gives each document's contents keyed by its path relative to the root, along with the
+/// referenced files. Files that don't declare a version are fragments, so they're left out.
+pub(crate) fn split(
+    files: Vec<PathBuf>,
+    root: &Path,
+) -> Result<(Vec<(String, String)>, ExternalFiles), OpenAPIAdapterErrors> {
+    let (parts, external) = read_files(files, root)?;
+    let documents = parts
+        .into_iter()
+        .filter(|(_, value)| is_document(value))
+        .map(|(key, value)| Ok((key, to_yaml(&value)?)))
+        .collect::<Result<_, OpenAPIAdapterErrors>>()?;
+    Ok((documents, external))
+}
+
+/// Reads the files, keyed by their path relative to the root, and the files they reference
+fn read_files(
+    files: Vec<PathBuf>,
+    root: &Path,
+) -> Result<(Vec<(String, Value)>, ExternalFiles), OpenAPIAdapterErrors> {
+    let mut external = IndexMap::new();
+    let mut parts = Vec::new();
     for file in files {
         if !(file.exists() && file.is_file()) {
             return Err(OpenAPIAdapterErrors::PathDoesNotExist(file));
         }
-        let contents = open_file(file)?;
-        hash = hash
-            .merge_str(&contents)
-            .map_err(|e| OpenAPIAdapterErrors::FailedToMerge(e.to_string()))?;
+        let is_json = file.extension() == Some("json".as_ref());
+        let contents = open_file(file.clone())?;
+        let mut value: Value = if is_json {
+            serde_json::from_str(&contents)
+                .map_err(|e| OpenAPIAdapterErrors::FailedToMerge(e.to_string()))?
+        } else {
+            serde_yaml::from_str(&contents)
+                .map_err(|e| OpenAPIAdapterErrors::FailedToMerge(e.to_string()))?
+        };
+        refs::bundle(&mut value, &file, root, &mut external);
+        parts.push((refs::file_key(&file, root)?, value));
     }
+    Ok((parts, external))
+}
+
+fn is_document(value: &Value) -> bool {
+    value.get("openapi").is_some() || value.get("swagger").is_some()
+}
 
-    Ok(hash.to_string())
+/// Whether a file is a JSON document that declares an OpenAPI or Swagger version. Files that
+/// can't be read or parsed, e.g. a `tsconfig.json` with comments, aren't
+pub(crate) fn is_json_document(file: &Path) -> bool {
+    open_file(file.to_path_buf())
+        .ok()
+        .and_then(|contents| serde_json::from_str::<Value>(&contents).ok())
+        .is_some_and(|value| is_document(&value))
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// Re-serializes a file as YAML, since not every JSON document is valid YAML (e.g. tab
+/// indentation)
+fn to_yaml(value: &Value) -> Result<String, OpenAPIAdapterErrors> {
+    serde_yaml::to_string(value).map_err(|e| OpenAPIAdapterErrors::FailedToMerge(e.to_string()))
+}
+
+#[derive(Debug, Clone)]
 pub struct Route {
     pub path: String,
+    /// The `$ref` the path item was reached through
+    pub reference: Option<String>,
+    /// Whether the path item's `$ref` couldn't be resolved, which leaves it without operations
+    pub unresolved: bool,
     pub get: Option<Operator>,
     pub post: Option<Operator>,
     pub put: Option<Operator>,
     pub delete: Option<Operator>,
     pub patch: Option<Operator>,
     pub options: Option<Operator>,
+    pub head: Option<Operator>,
+    pub trace: Option<Operator>,
+    /// Parameters shared by all of the path's operations
+    pub parameters: Vec<ReferenceOr<Parameter>>,
+    /// Servers declared on the path item itself
+    pub servers: Vec<Server>,
+    pub extensions: IndexMap<String, Value>,
+    /// Where the path is declared, e.g. `#/paths/~1users`. Callbacks don't have one
+    pub location: Option<String>,
+    pub document: Arc<Document>,
 }
 
-impl From<openapiv3::ReferenceOr<PathItem>> for Route {
-    fn from(value: openapiv3::ReferenceOr<PathItem>) -> Self {
-        match value {
-            openapiv3::ReferenceOr::Reference { reference: _ } => todo!("Implement reference path"),
-            openapiv3::ReferenceOr::Item(item) => Self {
-                path: "".to_string(),
-                get: item.get.map(|x| Operator::from_operation(&x, "GET")),
-                post: item.post.map(|x| Operator::from_operation(&x, "POST")),
-                put: item.put.map(|x| Operator::from_operation(&x, "PUT")),
-                delete: item.delete.map(|x| Operator::from_operation(&x, "DELETE")),
-                patch: item.patch.map(|x| Operator::from_operation(&x, "PATCH")),
-                options: item
-                    .options
-                    .map(|x| Operator::from_operation(&x, "OPTIONS")),
-            },
+impl Route {
+    pub fn from_path_item(
+        path: &str,
+        item: &PathItem,
+        reference: Option<String>,
+        location: Option<String>,
+        document: &Arc<Document>,
+    ) -> Self {
+        // The operations are wherever the path item's contents are, which can be another file
+        let item_location = reference.clone().or_else(|| location.clone());
+        let operator = |operation: &Option<Operation>, method: &str| {
+            operation.as_ref().map(|x| {
+                let mut operator = Operator::from_operation(x, method, item, document);
+                operator.location = item_location
+                    .as_ref()
+                    .map(|location| format!("{location}/{}", method.to_lowercase()));
+                operator
+            })
+        };
+        Self {
+            path: path.to_string(),
+            reference,
+            unresolved: false,
+            get: operator(&item.get, "GET"),
+            post: operator(&item.post, "POST"),
+            put: operator(&item.put, "PUT"),
+            delete: operator(&item.delete, "DELETE"),
+            patch: operator(&item.patch, "PATCH"),
+            options: operator(&item.options, "OPTIONS"),
+            head: operator(&item.head, "HEAD"),
+            trace: operator(&item.trace, "TRACE"),
+            parameters: item.parameters.clone(),
+            servers: item.servers.clone(),
+            extensions: item.extensions.clone(),
+            location,
+            document: document.clone(),
+        }
+    }
+
+    /// Where the path item's contents are, which can be another file
+    pub fn item_location(&self) -> Option<String> {
+        self.reference.clone().or_else(|| self.location.clone())
+    }
+
+    /// The path's operations, ordered by method
+    pub fn operations(&self) -> Vec<&Operator> {
+        [
+            &self.delete,
+            &self.get,
+            &self.head,
+            &self.options,
+            &self.patch,
+            &self.post,
+            &self.put,
+            &self.trace,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// The path item's servers, or else the document's
+    pub fn effective_servers(&self) -> Vec<Server> {
+        if self.servers.is_empty() {
+            self.document.openapi.servers.clone()
+        } else {
+            self.servers.clone()
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone)]
 pub struct Operator {
     pub method: String,
     pub summary: Option<String>,
     pub description: Option<String>,
     pub tags: Option<Vec<String>>,
-    pub aws: Option<AmazonApigatewayIntegration>,
+    pub aws: Option<Box<AmazonApigatewayIntegration>>,
+    pub operation: Operation,
+    /// Parameters of the path item the operation belongs to
+    pub path_parameters: Vec<ReferenceOr<Parameter>>,
+    /// Servers of the path item the operation belongs to
+    pub path_servers: Vec<Server>,
+    /// Where the operation is defined, e.g. `#/paths/~1users/get`. Callbacks' operations don't
+    /// have one
+    pub location: Option<String>,
+    pub document: Arc<Document>,
 }
 
 impl Operator {
-    pub fn from_operation(operation: &Operation, method: &str) -> Self {
+    pub fn from_operation(
+        operation: &Operation,
+        method: &str,
+        item: &PathItem,
+        document: &Arc<Document>,
+    ) -> Self {
         Self {
             method: method.to_string(),
             summary: operation.summary.clone(),
@@ -71,54 +238,361 @@ impl Operator {
             } else {
                 Some(operation.tags.clone())
             },
-            aws: match operation.extensions.get("x-amazon-apigateway-integration") {
-                Some(value) => {
-                    match serde_json::from_value::<AmazonApigatewayIntegration>(value.clone()) {
-                        Ok(mut s) => {
-                            s.extract_supplementary_data();
-                            Some(s)
-                        }
-                        Err(e) => {
-                            eprintln!("Failed to deserialize to AWS extension: {e} {value}");
-                            None
-                        }
-                    }
-                }
-                None => None,
-            },
+            // A malformed integration is reported by the document's diagnostics
+            aws: aws::extension::<AmazonApigatewayIntegration>(
+                &operation.extensions,
+                "x-amazon-apigateway-integration",
+            )
+            .map(|mut integration| {
+                integration.extract_supplementary_data();
+                Box::new(integration)
+            }),
+            operation: operation.clone(),
+            path_parameters: item.parameters.clone(),
+            path_servers: item.servers.clone(),
+            location: None,
+            document: document.clone(),
         }
     }
-}
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct AmazonApigatewayIntegration {
-    #[serde(rename = "type")]
-    pub r_type: String,
-    pub http_method: String,
-    pub uri: String,
-    #[serde(rename = "passthroughBehavior")]
-    pub pass_through_behavior: String,
-    pub timeout_in_millis: Option<usize>,
-    #[serde(skip)]
-    pub trigger: String,
-    #[serde(skip)]
-    pub arn: String,
-}
-
-impl AmazonApigatewayIntegration {
-    pub fn extract_supplementary_data(&mut self) {
-        let splits: Vec<&str> = self.uri.split(':').collect();
-        self.trigger = match splits[4] {
-            "lambda" => {
-                let x = splits.last().unwrap().split_once('{').unwrap();
-                self.arn = x.1.split_once('}').unwrap().0.to_string();
-                "Lambda".to_owned()
-            }
-            "state" => "Step Function".to_owned(),
-            x => x.to_owned(),
+    /// The operation's servers, or else the path item's, or else the document's
+    pub fn effective_servers(&self) -> Vec<Server> {
+        [&self.operation.servers, &self.path_servers]
+            .into_iter()
+            .find(|servers| !servers.is_empty())
+            .unwrap_or(&self.document.openapi.servers)
+            .clone()
+    }
+
+    /// The operation's `x-amazon-apigateway-request-validator`, or else the document's
+    pub fn request_validator(&self) -> Option<Named<RequestValidator>> {
+        let document = &self.document.openapi.extensions;
+        aws::request_validator(&self.operation.extensions, document)
+            .or_else(|| aws::request_validator(document, document))
+    }
+
+    /// The operation's security requirements, or else the document's
+    pub fn effective_security(&self) -> Vec<SecurityRequirement> {
+        self.operation
+            .security
+            .as_ref()
+            .or(self.document.openapi.security.as_ref())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The operation's parameters along with the path item's, where an operation parameter
+    /// overrides a path parameter with the same name and location
+    pub fn effective_parameters(&self) -> Vec<Resolved<Parameter>> {
+        let resolve = |parameters: &[ReferenceOr<Parameter>], location: Option<&str>| {
+            parameters
+                .iter()
+                .enumerate()
+                .filter_map(|(i, parameter)| {
+                    let parameter = self.document.resolve(parameter)?;
+                    Some(parameter.at(refs::join(location, &["parameters", &i.to_string()])))
+                })
+                .collect::<Vec<_>>()
         };
+        let operation = resolve(&self.operation.parameters, self.location.as_deref());
+        let overridden =
+            |parameter: &Parameter| operation.iter().any(|x| same_parameter(&x.item, parameter));
+        // The path item is the parent of the operation
+        let path_item = self
+            .location
+            .as_deref()
+            .and_then(|location| location.rsplit_once('/'))
+            .map(|(path_item, _)| path_item);
+        resolve(&self.path_parameters, path_item)
+            .into_iter()
+            .filter(|parameter| !overridden(&parameter.item))
+            .chain(operation.iter().cloned())
+            .collect()
     }
+
+    /// The operation's request body
+    pub fn request_body(&self) -> Option<Resolved<RequestBody>> {
+        let request_body = self.operation.request_body.as_ref()?;
+        let location = refs::join(self.location.as_deref(), &["requestBody"]);
+        Some(self.document.resolve(request_body)?.at(location))
+    }
+
+    /// The operation's responses by status code, with the default response last
+    pub fn responses(&self) -> Vec<Named<Resolved<Response>>> {
+        let responses = &self.operation.responses;
+        responses
+            .responses
+            .iter()
+            .map(|(status_code, response)| (status_code.to_string(), response))
+            .chain(
+                responses
+                    .default
+                    .iter()
+                    .map(|response| ("default".to_string(), response)),
+            )
+            .filter_map(|(status_code, response)| {
+                let location = refs::join(self.location.as_deref(), &["responses", &status_code]);
+                Some(Named {
+                    item: self.document.resolve(response)?.at(location),
+                    name: status_code,
+                })
+            })
+            .collect()
+    }
+}
+
+/// A server's URL with each `{variable}` replaced by the variable's default
+pub fn expand_server_url(server: &Server) -> String {
+    let mut url = server.url.clone();
+    for (name, variable) in server.variables.iter().flatten() {
+        url = url.replace(&format!("{{{name}}}"), &variable.default);
+    }
+    url
+}
+
+/// A problem found while loading a document, which doesn't stop it from being queried
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// What kind of problem it is, e.g. `unresolved-template-variable`
+    pub kind: String,
+    /// What the problem is about, e.g. the variable's name
+    pub name: String,
+    pub message: String,
+}
+
+/// An entry of one of the `components` sections, e.g. a parameter in `components.parameters`
+#[derive(Debug, Clone)]
+pub struct Component {
+    /// The section it's in, e.g. `parameters`
+    pub kind: String,
+    pub name: String,
+    pub document: Arc<Document>,
+    pub location: Option<String>,
+}
+
+/// An item from a map along with its key, e.g. a response and its status code
+#[derive(Debug, Clone)]
+pub struct Named<T> {
+    pub name: String,
+    pub item: T,
+}
+
+/// A schema, along with the name it's defined under in `components.schemas`
+#[derive(Debug, Clone)]
+pub struct SchemaNode {
+    pub name: Option<String>,
+    pub schema: Resolved<Schema>,
+    /// The schema as JSON, which keywords are read from whatever kind of schema it is
+    pub json: Value,
+    /// Where the schema is defined, when it's known
+    pub location: Option<String>,
+}
+
+impl SchemaNode {
+    pub fn new(name: Option<String>, schema: Resolved<Schema>) -> Self {
+        let json = serde_json::to_value(&schema.item).unwrap_or_default();
+        let location = schema.location.clone();
+        Self {
+            name,
+            schema,
+            json,
+            location,
+        }
+    }
+
+    /// The schema a nested JSON value holds, e.g. one of the `allOf` schemas, which is under
+    /// the keys within this one
+    pub fn child(&self, value: &Value, keys: &[&str]) -> Option<SchemaNode> {
+        let schema = serde_json::from_value(value.clone()).ok()?;
+        let location = refs::join(self.location.as_deref(), keys);
+        let schema = self.schema.document.resolve_schema(&schema)?.at(location);
+        Some(SchemaNode::new(None, schema))
+    }
+}
+
+/// An example of a parameter, media type or schema, with the schema its value should match
+#[derive(Debug, Clone)]
+pub struct ExampleNode {
+    pub example: Named<Resolved<Example>>,
+    pub schema: Option<SchemaNode>,
+}
+
+/// A property of an object schema
+#[derive(Debug, Clone)]
+pub struct SchemaProperty {
+    pub name: String,
+    /// Whether the property is in the object's `required` list
+    pub required: bool,
+    pub schema: Option<SchemaNode>,
+}
+
+/// One of a security scheme's OAuth2 flows, with the URLs that apply to it
+#[derive(Debug, Clone)]
+pub struct OAuthFlow {
+    /// implicit, password, clientCredentials or authorizationCode
+    pub flow_type: String,
+    pub authorization_url: Option<String>,
+    pub token_url: Option<String>,
+    pub refresh_url: Option<String>,
+    pub scopes: IndexMap<String, String>,
+}
+
+impl OAuthFlow {
+    pub fn from_flows(flows: &OAuth2Flows) -> Vec<Self> {
+        let mut result = Vec::new();
+        if let Some(flow) = &flows.implicit {
+            result.push(Self {
+                flow_type: "implicit".to_string(),
+                authorization_url: Some(flow.authorization_url.clone()),
+                token_url: None,
+                refresh_url: flow.refresh_url.clone(),
+                scopes: flow.scopes.clone(),
+            });
+        }
+        if let Some(flow) = &flows.password {
+            result.push(Self {
+                flow_type: "password".to_string(),
+                authorization_url: None,
+                token_url: Some(flow.token_url.clone()),
+                refresh_url: flow.refresh_url.clone(),
+                scopes: flow.scopes.clone(),
+            });
+        }
+        if let Some(flow) = &flows.client_credentials {
+            result.push(Self {
+                flow_type: "clientCredentials".to_string(),
+                authorization_url: None,
+                token_url: Some(flow.token_url.clone()),
+                refresh_url: flow.refresh_url.clone(),
+                scopes: flow.scopes.clone(),
+            });
+        }
+        if let Some(flow) = &flows.authorization_code {
+            result.push(Self {
+                flow_type: "authorizationCode".to_string(),
+                authorization_url: Some(flow.authorization_url.clone()),
+                token_url: Some(flow.token_url.clone()),
+                refresh_url: flow.refresh_url.clone(),
+                scopes: flow.scopes.clone(),
+            });
+        }
+        result
+    }
+}
+
+/// A scheme that a security requirement needs, along with the scopes it needs
+#[derive(Debug, Clone)]
+pub struct SecuritySchemeRequirement {
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub document: Arc<Document>,
+}
+
+/// The schema of a parameter or header, or of its first media type when it uses `content`
+pub(crate) fn schema_or_content(
+    format: &ParameterSchemaOrContent,
+) -> Option<(&ReferenceOr<Schema>, Vec<&str>)> {
+    match format {
+        ParameterSchemaOrContent::Schema(schema) => Some((schema, vec!["schema"])),
+        ParameterSchemaOrContent::Content(content) => {
+            let (name, media_type) = content.first()?;
+            Some((
+                media_type.schema.as_ref()?,
+                vec!["content", name.as_str(), "schema"],
+            ))
+        }
+    }
+}
+
+/// The schema of a parameter, or of its first media type when it uses `content`
+pub(crate) fn parameter_schema(parameter: &Resolved<Parameter>) -> Option<SchemaNode> {
+    let format = &parameter.item.parameter_data_ref().format;
+    let (schema, keys) = schema_or_content(format)?;
+    let location = refs::join(parameter.location.as_deref(), &keys);
+    let schema = parameter.document.resolve_schema(schema)?.at(location);
+    Some(SchemaNode::new(None, schema))
+}
+
+/// Where a parameter goes in the request: path, query, header or cookie
+pub(crate) fn parameter_location(parameter: &Parameter) -> &'static str {
+    match parameter {
+        Parameter::Query { .. } => "query",
+        Parameter::Header { .. } => "header",
+        Parameter::Path { .. } => "path",
+        Parameter::Cookie { .. } => "cookie",
+    }
+}
+
+/// The media types of a request body or response's content, which is at the location
+pub(crate) fn media_types(
+    content: &IndexMap<String, MediaType>,
+    location: Option<&str>,
+    document: &Arc<Document>,
+) -> Vec<Named<Resolved<MediaType>>> {
+    content
+        .iter()
+        .map(|(name, media_type)| Named {
+            name: name.clone(),
+            item: Resolved {
+                item: media_type.clone(),
+                reference: None,
+                document: document.clone(),
+                location: refs::join(location, &["content", name]),
+            },
+        })
+        .collect()
+}
+
+/// The schema of a media type
+pub(crate) fn media_type_schema(media_type: &Resolved<MediaType>) -> Option<SchemaNode> {
+    let location = refs::join(media_type.location.as_deref(), &["schema"]);
+    let schema = media_type.item.schema.as_ref()?;
+    let schema = media_type.document.resolve_schema(schema)?.at(location);
+    Some(SchemaNode::new(None, schema))
+}
+
+/// The `example` and `examples` at the location, each with the schema it should match. A single
+/// `example` is named after its key
+pub(crate) fn examples(
+    example: Option<&Value>,
+    examples: &IndexMap<String, ReferenceOr<Example>>,
+    location: Option<&str>,
+    document: &Arc<Document>,
+    schema: Option<SchemaNode>,
+) -> Vec<ExampleNode> {
+    let example = example.map(|value| Named {
+        name: "example".to_string(),
+        item: Resolved {
+            item: Example {
+                value: Some(value.clone()),
+                ..Default::default()
+            },
+            reference: None,
+            document: document.clone(),
+            location: refs::join(location, &["example"]),
+        },
+    });
+    let examples = examples.iter().filter_map(|(name, example)| {
+        Some(Named {
+            name: name.clone(),
+            item: document
+                .resolve(example)?
+                .at(refs::join(location, &["examples", name])),
+        })
+    });
+    example
+        .into_iter()
+        .chain(examples)
+        .map(|example| ExampleNode {
+            example,
+            schema: schema.clone(),
+        })
+        .collect()
+}
+
+fn same_parameter(a: &Parameter, b: &Parameter) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+        && a.parameter_data_ref().name == b.parameter_data_ref().name
 }
 
 pub(crate) fn find_files(path: &std::path::Path, extension: &OsStr) -> Vec<PathBuf> {
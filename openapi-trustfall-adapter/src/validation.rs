@@ -0,0 +1,363 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Mutex, OnceLock},
+};
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::{
+    refs::escape,
+    utils::{ExampleNode, SchemaNode},
+};
+
+/// A way an example's value doesn't match its schema
+#[derive(Debug, Clone)]
+pub struct ValidationError {
+    /// JSON pointer to the value within the example, empty for the whole value
+    pub instance_path: String,
+    /// The schema keyword the value fails, e.g. `type` or `required`
+    pub keyword: &'static str,
+    pub message: String,
+}
+
+/// Compiled `pattern`s, or None for ones that aren't valid regexes, shared by every example
+static PATTERNS: OnceLock<Mutex<HashMap<String, Option<Regex>>>> = OnceLock::new();
+
+/// Checks an example's value against its schema. An example with no value, such as one given
+/// by `externalValue`, or with no schema has nothing to check.
+pub(crate) fn validate_example(example: &ExampleNode) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    if let (Some(schema), Some(value)) = (&example.schema, &example.example.item.item.value) {
+        Validator::default().validate(schema, value, "", &mut errors);
+    }
+    errors
+}
+
+/// Walks a schema alongside a value
+#[derive(Default)]
+struct Validator {
+    /// The schema locations being checked, with the instance path they're checked at. A schema
+    /// that refers back to itself, e.g. through `allOf`, is only checked once at each path
+    visiting: HashSet<(String, String)>,
+}
+
+impl Validator {
+    /// Adds the ways the value at the instance path doesn't match the schema to the errors
+    fn validate(
+        &mut self,
+        schema: &SchemaNode,
+        value: &Value,
+        path: &str,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let key = schema
+            .location
+            .clone()
+            .map(|location| (location, path.to_string()));
+        if let Some(key) = &key {
+            if !self.visiting.insert(key.clone()) {
+                return;
+            }
+        }
+        self.validate_schema(schema, value, path, errors);
+        if let Some(key) = &key {
+            self.visiting.remove(key);
+        }
+    }
+
+    /// Whether the value at the instance path matches the schema
+    fn is_valid(&mut self, schema: &SchemaNode, value: &Value, path: &str) -> bool {
+        let mut errors = Vec::new();
+        self.validate(schema, value, path, &mut errors);
+        errors.is_empty()
+    }
+
+    fn validate_schema(
+        &mut self,
+        schema: &SchemaNode,
+        value: &Value,
+        path: &str,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let json = &schema.json;
+        let mut error = |keyword, message| {
+            errors.push(ValidationError {
+                instance_path: path.to_string(),
+                keyword,
+                message,
+            })
+        };
+        let schema_type = json.get("type").and_then(Value::as_str);
+        if value.is_null() {
+            // Without a type any value is allowed, as in JSON Schema
+            if schema_type.is_some() && json.get("nullable") != Some(&Value::Bool(true)) {
+                error("nullable", "null isn't allowed".to_string());
+            }
+            return;
+        }
+        if let Some(expected) = schema_type.filter(|expected| !has_type(value, expected)) {
+            let found = type_name(value);
+            error("type", format!("expected {expected}, found {found}"));
+            return;
+        }
+        let values = json.get("enum").and_then(Value::as_array);
+        if values.is_some_and(|values| !values.contains(value)) {
+            error("enum", format!("{value} isn't one of the allowed values"));
+        }
+        match value {
+            Value::Number(number) => {
+                let number = number.as_f64().unwrap_or_default();
+                for (keyword, message) in number_errors(json, number) {
+                    error(keyword, message);
+                }
+            }
+            Value::String(string) => {
+                for (keyword, message) in string_errors(json, string) {
+                    error(keyword, message);
+                }
+            }
+            Value::Array(items) => {
+                for (keyword, message) in array_errors(json, items) {
+                    error(keyword, message);
+                }
+                let items_schema = json
+                    .get("items")
+                    .and_then(|items| schema.child(items, &["items"]));
+                for (i, item) in items.iter().enumerate() {
+                    if let Some(items_schema) = &items_schema {
+                        self.validate(items_schema, item, &format!("{path}/{i}"), errors);
+                    }
+                }
+            }
+            Value::Object(object) => self.validate_object(schema, object, path, errors),
+            _ => {}
+        }
+        self.validate_combinations(schema, value, path, errors);
+    }
+
+    fn validate_object(
+        &mut self,
+        schema: &SchemaNode,
+        object: &serde_json::Map<String, Value>,
+        path: &str,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let json = &schema.json;
+        let mut error = |keyword, message| {
+            errors.push(ValidationError {
+                instance_path: path.to_string(),
+                keyword,
+                message,
+            })
+        };
+        let required = json.get("required").and_then(Value::as_array);
+        for name in required.into_iter().flatten().filter_map(Value::as_str) {
+            if !object.contains_key(name) {
+                error("required", format!("missing required property '{name}'"));
+            }
+        }
+        let count = object.len() as f64;
+        if let Some(min) = bound(json, "minProperties").filter(|min| count < *min) {
+            error(
+                "minProperties",
+                format!("has {count} properties, fewer than {min}"),
+            );
+        }
+        if let Some(max) = bound(json, "maxProperties").filter(|max| count > *max) {
+            error(
+                "maxProperties",
+                format!("has {count} properties, more than {max}"),
+            );
+        }
+        let properties = json.get("properties").and_then(Value::as_object);
+        let additional = json.get("additionalProperties");
+        for (name, value) in object {
+            let property_path = format!("{path}/{}", escape(name));
+            let property_schema = match properties.and_then(|properties| properties.get(name)) {
+                Some(property) => schema.child(property, &["properties", name]),
+                None if additional == Some(&Value::Bool(false)) => {
+                    errors.push(ValidationError {
+                        instance_path: path.to_string(),
+                        keyword: "additionalProperties",
+                        message: format!("property '{name}' isn't allowed"),
+                    });
+                    None
+                }
+                None => additional
+                    .filter(|additional| additional.is_object())
+                    .and_then(|additional| schema.child(additional, &["additionalProperties"])),
+            };
+            if let Some(property_schema) = property_schema {
+                self.validate(&property_schema, value, &property_path, errors);
+            }
+        }
+    }
+
+    /// Checks `allOf`, `anyOf`, `oneOf` and `not`
+    fn validate_combinations(
+        &mut self,
+        schema: &SchemaNode,
+        value: &Value,
+        path: &str,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let children = |keyword: &str| -> Vec<SchemaNode> {
+            schema
+                .json
+                .get(keyword)
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .filter_map(|(i, child)| schema.child(child, &[keyword, &i.to_string()]))
+                .collect()
+        };
+        for child in children("allOf") {
+            self.validate(&child, value, path, errors);
+        }
+        let any_of = children("anyOf");
+        if !any_of.is_empty() && !any_of.iter().any(|child| self.is_valid(child, value, path)) {
+            errors.push(ValidationError {
+                instance_path: path.to_string(),
+                keyword: "anyOf",
+                message: "doesn't match any of the anyOf schemas".to_string(),
+            });
+        }
+        let one_of = children("oneOf");
+        let matches = one_of
+            .iter()
+            .filter(|child| self.is_valid(child, value, path))
+            .count();
+        if !one_of.is_empty() && matches != 1 {
+            errors.push(ValidationError {
+                instance_path: path.to_string(),
+                keyword: "oneOf",
+                message: format!("matches {matches} of the oneOf schemas rather than exactly one"),
+            });
+        }
+        let not = schema
+            .json
+            .get("not")
+            .and_then(|not| schema.child(not, &["not"]));
+        if not.is_some_and(|not| self.is_valid(&not, value, path)) {
+            errors.push(ValidationError {
+                instance_path: path.to_string(),
+                keyword: "not",
+                message: "matches the schema it mustn't".to_string(),
+            });
+        }
+    }
+}
+
+fn number_errors(json: &Value, number: f64) -> Vec<(&'static str, String)> {
+    let mut errors = Vec::new();
+    let is_exclusive = |keyword| json.get(keyword) == Some(&Value::Bool(true));
+    if let Some(min) = bound(json, "minimum") {
+        if is_exclusive("exclusiveMinimum") && number <= min {
+            errors.push(("minimum", format!("{number} isn't greater than {min}")));
+        } else if number < min {
+            errors.push(("minimum", format!("{number} is less than {min}")));
+        }
+    }
+    if let Some(max) = bound(json, "maximum") {
+        if is_exclusive("exclusiveMaximum") && number >= max {
+            errors.push(("maximum", format!("{number} isn't less than {max}")));
+        } else if number > max {
+            errors.push(("maximum", format!("{number} is greater than {max}")));
+        }
+    }
+    let multiple_of = bound(json, "multipleOf").filter(|multiple| *multiple > 0.0);
+    if let Some(multiple) = multiple_of.filter(|multiple| (number / multiple).fract() != 0.0) {
+        errors.push((
+            "multipleOf",
+            format!("{number} isn't a multiple of {multiple}"),
+        ));
+    }
+    errors
+}
+
+fn string_errors(json: &Value, string: &str) -> Vec<(&'static str, String)> {
+    let mut errors = Vec::new();
+    let length = string.chars().count() as f64;
+    if let Some(min) = bound(json, "minLength").filter(|min| length < *min) {
+        errors.push((
+            "minLength",
+            format!("is {length} characters, fewer than {min}"),
+        ));
+    }
+    if let Some(max) = bound(json, "maxLength").filter(|max| length > *max) {
+        errors.push((
+            "maxLength",
+            format!("is {length} characters, more than {max}"),
+        ));
+    }
+    // A pattern that isn't a valid regex can't be checked
+    let pattern = json.get("pattern").and_then(Value::as_str);
+    if let Some(pattern) =
+        pattern.filter(|pattern| compile(pattern).is_some_and(|regex| !regex.is_match(string)))
+    {
+        errors.push(("pattern", format!("'{string}' doesn't match {pattern}")));
+    }
+    errors
+}
+
+fn array_errors(json: &Value, items: &[Value]) -> Vec<(&'static str, String)> {
+    let mut errors = Vec::new();
+    let count = items.len() as f64;
+    if let Some(min) = bound(json, "minItems").filter(|min| count < *min) {
+        errors.push(("minItems", format!("has {count} items, fewer than {min}")));
+    }
+    if let Some(max) = bound(json, "maxItems").filter(|max| count > *max) {
+        errors.push(("maxItems", format!("has {count} items, more than {max}")));
+    }
+    let is_unique = |(i, item): (usize, &Value)| !items[..i].contains(item);
+    if json.get("uniqueItems") == Some(&Value::Bool(true))
+        && !items.iter().enumerate().all(is_unique)
+    {
+        errors.push(("uniqueItems", "has duplicate items".to_string()));
+    }
+    errors
+}
+
+/// The regex for a `pattern`, compiled the first time it's used
+fn compile(pattern: &str) -> Option<Regex> {
+    let mut patterns = PATTERNS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    patterns
+        .entry(pattern.to_string())
+        .or_insert_with(|| Regex::new(pattern).ok())
+        .clone()
+}
+
+/// A numeric keyword of the schema, e.g. `minimum`
+fn bound(json: &Value, keyword: &str) -> Option<f64> {
+    json.get(keyword).and_then(Value::as_f64)
+}
+
+/// Whether the value is of an OpenAPI type. Types that aren't known match anything
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "integer" => value.as_f64().is_some_and(|number| number.fract() == 0.0),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
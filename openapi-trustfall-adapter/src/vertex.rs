@@ -1,13 +1,113 @@
-use super::utils::{AmazonApigatewayIntegration, Operator, Route};
+use std::sync::Arc;
+
+use super::{
+    aws::{
+        AmazonApigatewayAuthorizer, AmazonApigatewayCors, AmazonApigatewayIntegration,
+        GatewayResponse, IntegrationArn, IntegrationResponse, RequestValidator,
+    },
+    diff::Change,
+    document::Document,
+    provenance::MergeConflict,
+    refs::Resolved,
+    utils::{
+        Component, Diagnostic, ExampleNode, Named, OAuthFlow, Operator, Route, SchemaNode,
+        SchemaProperty, SecuritySchemeRequirement,
+    },
+    validation::ValidationError,
+};
 
 #[non_exhaustive]
 #[derive(Debug, Clone, trustfall::provider::TrustfallEnumVertex)]
 pub enum Vertex {
+    AmazonApigatewayAuthorizer(AmazonApigatewayAuthorizer),
+    AmazonApigatewayCors(AmazonApigatewayCors),
+    /// A gateway response and its response type
+    AmazonApigatewayGatewayResponse(Named<GatewayResponse>),
     AmazonApigatewayIntegration(AmazonApigatewayIntegration),
+    AmazonApigatewayIntegrationArn(IntegrationArn),
+    /// An integration response and its selection pattern
+    AmazonApigatewayIntegrationResponse(Named<IntegrationResponse>),
+    /// A request or response parameter mapping, or a mapping template keyed by content type
+    AmazonApigatewayMapping(Named<String>),
+    AmazonApigatewayRequestValidator(Named<RequestValidator>),
+    ApiKeySecurityScheme(Named<Resolved<openapiv3::SecurityScheme>>),
+    /// A callback's name, with its expression as the route's path
+    Callback(Named<Box<Route>>),
+    Change(Change),
+    Component(Component),
+    Diagnostic(Diagnostic),
+    Document(Arc<Document>),
+    Example(ExampleNode),
+    /// A vendor extension's name and value
+    Extension(Named<serde_json::Value>),
+    ExternalDocs(openapiv3::ExternalDocumentation),
+    Header(Named<Resolved<openapiv3::Header>>),
+    HttpSecurityScheme(Named<Resolved<openapiv3::SecurityScheme>>),
     Info(openapiv3::Info),
+    /// A JSON value with its key in the parent object, or its index in the parent array
+    JsonValue(Named<serde_json::Value>),
+    Link(Named<Resolved<openapiv3::Link>>),
+    MediaType(Named<Resolved<openapiv3::MediaType>>),
+    MergeConflict(MergeConflict),
+    OAuth2SecurityScheme(Named<Resolved<openapiv3::SecurityScheme>>),
+    OAuthFlow(OAuthFlow),
+    /// A scope's name and description
+    OAuthScope(Named<String>),
+    OpenIdConnectSecurityScheme(Named<Resolved<openapiv3::SecurityScheme>>),
     Operation(Operator),
-    Path(Route),
+    Parameter(Resolved<openapiv3::Parameter>),
+    Path(Box<Route>),
     Paths(Vec<Route>),
+    RequestBody(Resolved<openapiv3::RequestBody>),
+    Response(Named<Resolved<openapiv3::Response>>),
+    Schema(SchemaNode),
+    SchemaProperty(SchemaProperty),
+    SecurityRequirement(Resolved<openapiv3::SecurityRequirement>),
+    SecuritySchemeRequirement(SecuritySchemeRequirement),
+    Server(openapiv3::Server),
+    ServerVariable(Named<openapiv3::ServerVariable>),
     Tags(Vec<openapiv3::Tag>),
-    Tag(openapiv3::Tag),
+    Tag(Resolved<openapiv3::Tag>),
+    ValidationError(ValidationError),
+}
+
+impl Vertex {
+    /// The vertex for a security scheme, which has a different type for each kind of scheme
+    pub(crate) fn security_scheme(scheme: Named<Resolved<openapiv3::SecurityScheme>>) -> Self {
+        match scheme.item.item {
+            openapiv3::SecurityScheme::APIKey { .. } => Vertex::ApiKeySecurityScheme(scheme),
+            openapiv3::SecurityScheme::HTTP { .. } => Vertex::HttpSecurityScheme(scheme),
+            openapiv3::SecurityScheme::OAuth2 { .. } => Vertex::OAuth2SecurityScheme(scheme),
+            openapiv3::SecurityScheme::OpenIDConnect { .. } => {
+                Vertex::OpenIdConnectSecurityScheme(scheme)
+            }
+        }
+    }
+
+    /// The document and location of any `Located` vertex
+    pub(crate) fn as_located(&self) -> Option<(&Arc<Document>, &Option<String>)> {
+        match self {
+            Vertex::Component(component) => Some((&component.document, &component.location)),
+            Vertex::Operation(operation) => Some((&operation.document, &operation.location)),
+            Vertex::Parameter(parameter) => Some((&parameter.document, &parameter.location)),
+            Vertex::Path(route) => Some((&route.document, &route.location)),
+            Vertex::Response(response) => Some((&response.item.document, &response.item.location)),
+            Vertex::Schema(schema) => Some((&schema.schema.document, &schema.location)),
+            Vertex::Tag(tag) => Some((&tag.document, &tag.location)),
+            _ => None,
+        }
+    }
+
+    /// The underlying scheme of any `SecurityScheme` vertex
+    pub(crate) fn as_any_security_scheme(
+        &self,
+    ) -> Option<&Named<Resolved<openapiv3::SecurityScheme>>> {
+        match self {
+            Vertex::ApiKeySecurityScheme(scheme)
+            | Vertex::HttpSecurityScheme(scheme)
+            | Vertex::OAuth2SecurityScheme(scheme)
+            | Vertex::OpenIdConnectSecurityScheme(scheme) => Some(scheme),
+            _ => None,
+        }
+    }
 }